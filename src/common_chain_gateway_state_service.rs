@@ -2,15 +2,81 @@ use anyhow::{anyhow, Context, Result};
 use ethers::abi::{decode, ParamType, Token};
 use ethers::prelude::*;
 use ethers::utils::keccak256;
-use log::error;
+use log::{error, warn};
 use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::RwLock;
 use tokio::time;
 
 use crate::common_chain_util::get_block_number_by_timestamp;
-use crate::constant::GATEWAY_BLOCK_STATES_TO_MAINTAIN;
+use crate::constant::{GATEWAY_STAKE_ADJUSTMENT_FACTOR, MAX_RETRY_ON_PROVIDER_ERROR};
+use crate::metrics::GatewayStateMetrics;
+use crate::reorg::{record_cycle_block_hash, repair_reorg};
+use crate::state_store::{open_state_store, CycleBlockHash, StateStore};
+
+/// Fetch logs for `[from_block_number, to_block_number]` in fixed-size
+/// windows (width given by `max_block_range`), since most RPC providers
+/// reject a range that is too wide or that would return too many results.
+/// Each window is retried independently with exponential backoff so a
+/// single transient provider error doesn't force a re-fetch of the whole
+/// cycle.
+async fn fetch_logs_with_retry(
+    provider: &Provider<Http>,
+    contract_address: Address,
+    topics: Vec<[u8; 32]>,
+    from_block_number: u64,
+    to_block_number: u64,
+    max_block_range: u64,
+    metrics: &Arc<GatewayStateMetrics>,
+) -> Result<Vec<Log>> {
+    let mut logs = vec![];
+    let mut window_start = from_block_number;
+
+    while window_start <= to_block_number {
+        let window_end = (window_start + max_block_range - 1).min(to_block_number);
+        let event_filter = Filter::new()
+            .address(contract_address)
+            .from_block(window_start)
+            .to_block(window_end)
+            .topic0(topics.clone());
+
+        let mut attempt = 0;
+        loop {
+            let fetch_timer = metrics.log_fetch_latency.start_timer();
+            let result = provider.get_logs(&event_filter).await;
+            fetch_timer.observe_duration();
+
+            match result {
+                Ok(window_logs) => {
+                    logs.extend(window_logs);
+                    break;
+                }
+                Err(err) => {
+                    metrics.provider_errors.with_label_values(&["get_logs"]).inc();
+                    attempt += 1;
+                    if attempt >= MAX_RETRY_ON_PROVIDER_ERROR {
+                        return Err(anyhow!(err).context(format!(
+                            "Failed to get logs for blocks {}..={} after {} attempts",
+                            window_start, window_end, attempt
+                        )));
+                    }
+                    let backoff = Duration::from_secs(2u64.pow(attempt as u32));
+                    warn!(
+                        "get_logs failed for blocks {}..={} (attempt {}/{}): {:?}, retrying in {:?}",
+                        window_start, window_end, attempt, MAX_RETRY_ON_PROVIDER_ERROR, err, backoff
+                    );
+                    time::sleep(backoff).await;
+                }
+            }
+        }
+
+        window_start = window_end + 1;
+    }
+
+    Ok(logs)
+}
 
 #[derive(Debug, Clone)]
 pub struct GatewayData {
@@ -22,6 +88,66 @@ pub struct GatewayData {
     pub req_chain_ids: BTreeSet<U256>,
 }
 
+/// Per-contract epoch state, keyed by the gateway-registry contract address
+/// it was indexed from. Each contract gets its own independent indexing
+/// task and its own keyed subtree of state, so one deployment can track
+/// several gateway-registry contracts (e.g. staging + production, or
+/// multiple request chains) at once.
+pub type MultiContractGatewayEpochState =
+    Arc<RwLock<BTreeMap<Address, Arc<RwLock<BTreeMap<u64, BTreeMap<Bytes, GatewayData>>>>>>>;
+
+/// Spawn an independent `gateway_epoch_state_service` task per contract in
+/// `contract_addresses`, each with its own state subtree and its own
+/// `StateStore` (namespaced under `state_store_base_path` by contract
+/// address), and return the combined, queryable state.
+pub async fn multi_contract_gateway_epoch_state_service(
+    contract_addresses: Vec<Address>,
+    provider: Provider<Http>,
+    epoch: u64,
+    time_interval: u64,
+    gateway_block_states_to_maintain: u64,
+    state_store_kind: &str,
+    state_store_base_path: &Path,
+    metrics: Arc<GatewayStateMetrics>,
+    max_block_range: u64,
+    reorg_depth: u64,
+) -> Result<MultiContractGatewayEpochState> {
+    let all_contracts_state: MultiContractGatewayEpochState = Arc::new(RwLock::new(BTreeMap::new()));
+
+    for contract_address in contract_addresses {
+        let contract_state = Arc::new(RwLock::new(BTreeMap::new()));
+        all_contracts_state
+            .write()
+            .await
+            .insert(contract_address, contract_state.clone());
+
+        let state_store = open_state_store(
+            state_store_kind,
+            &state_store_base_path.join(format!("{:?}", contract_address)),
+        )?;
+
+        let provider = provider.clone();
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            gateway_epoch_state_service(
+                contract_address,
+                &provider,
+                &contract_state,
+                epoch,
+                time_interval,
+                state_store,
+                metrics,
+                max_block_range,
+                reorg_depth,
+                gateway_block_states_to_maintain,
+            )
+            .await;
+        });
+    }
+
+    Ok(all_contracts_state)
+}
+
 // Initialize the gateway epoch state
 pub async fn gateway_epoch_state_service(
     contract_address: Address,
@@ -29,7 +155,22 @@ pub async fn gateway_epoch_state_service(
     gateway_epoch_state: &Arc<RwLock<BTreeMap<u64, BTreeMap<Bytes, GatewayData>>>>,
     epoch: u64,
     time_interval: u64,
+    state_store: Arc<dyn StateStore>,
+    metrics: Arc<GatewayStateMetrics>,
+    max_block_range: u64,
+    reorg_depth: u64,
+    gateway_block_states_to_maintain: u64,
 ) {
+    let cycle_block_hashes: Arc<RwLock<BTreeMap<u64, CycleBlockHash>>> =
+        Arc::new(RwLock::new(BTreeMap::new()));
+
+    // Resume from the last persisted cycle instead of always rebuilding
+    // gateway_block_states_to_maintain cycles of history from block 0.
+    let resume_cycle = state_store
+        .latest_cycle()
+        .await
+        .unwrap_or(None);
+
     let current_cycle = (SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
@@ -38,27 +179,75 @@ pub async fn gateway_epoch_state_service(
         / time_interval;
 
     let initial_epoch_cycle: u64;
-    if current_cycle >= GATEWAY_BLOCK_STATES_TO_MAINTAIN {
-        initial_epoch_cycle = current_cycle - GATEWAY_BLOCK_STATES_TO_MAINTAIN + 1;
+    if let Some(resume_cycle) = resume_cycle {
+        initial_epoch_cycle = resume_cycle + 1;
+    } else if current_cycle >= gateway_block_states_to_maintain {
+        initial_epoch_cycle = current_cycle - gateway_block_states_to_maintain + 1;
     } else {
         initial_epoch_cycle = 1;
     };
+
+    if let Some(resume_cycle) = resume_cycle {
+        if let Ok(Some(state)) = state_store.get_cycle(resume_cycle).await {
+            gateway_epoch_state
+                .write()
+                .await
+                .insert(resume_cycle, state);
+        }
+    }
+
+    // Rehydrate cycle_block_hashes from the store too, so repair_reorg has
+    // something to compare the resumed cycles against instead of starting
+    // with an empty map and missing a reorg until fresh cycles accumulate.
+    if let Ok(persisted_hashes) = state_store.all_cycle_block_hashes().await {
+        *cycle_block_hashes.write().await = persisted_hashes;
+    }
+
     {
         let contract_address_clone = contract_address.clone();
         let provider_clone = provider.clone();
         let gateway_epoch_state_clone = Arc::clone(gateway_epoch_state);
+        let state_store_clone = state_store.clone();
+        let metrics_clone = metrics.clone();
+        let cycle_block_hashes_clone = cycle_block_hashes.clone();
         tokio::spawn(async move {
             for cycle_number in initial_epoch_cycle..=current_cycle {
-                generate_gateway_epoch_state_for_cycle(
-                    contract_address_clone,
-                    &provider_clone,
-                    &gateway_epoch_state_clone,
-                    cycle_number,
-                    epoch,
-                    time_interval,
-                )
-                .await
-                .unwrap();
+                let mut attempt = 0;
+                loop {
+                    let result = generate_gateway_epoch_state_for_cycle(
+                        contract_address_clone,
+                        &provider_clone,
+                        &gateway_epoch_state_clone,
+                        cycle_number,
+                        epoch,
+                        time_interval,
+                        &state_store_clone,
+                        &metrics_clone,
+                        max_block_range,
+                        &cycle_block_hashes_clone,
+                        reorg_depth,
+                    )
+                    .await;
+
+                    let Err(err) = result else {
+                        break;
+                    };
+
+                    attempt += 1;
+                    if attempt >= MAX_RETRY_ON_PROVIDER_ERROR {
+                        error!(
+                            "Giving up on gateway epoch state for cycle {} during catch-up after {} attempts: {:?}",
+                            cycle_number, attempt, err
+                        );
+                        break;
+                    }
+                    let backoff = Duration::from_secs(2u64.pow(attempt as u32));
+                    warn!(
+                        "Failed to generate gateway epoch state for cycle {} during catch-up (attempt {}/{}): {:?}, retrying in {:?}",
+                        cycle_number, attempt, MAX_RETRY_ON_PROVIDER_ERROR, err, backoff
+                    );
+                    time::sleep(backoff).await;
+                }
             }
         });
     }
@@ -80,24 +269,55 @@ pub async fn gateway_epoch_state_service(
     loop {
         interval.tick().await;
 
-        generate_gateway_epoch_state_for_cycle(
-            contract_address,
-            provider,
+        let mut attempt = 0;
+        loop {
+            let result = generate_gateway_epoch_state_for_cycle(
+                contract_address,
+                provider,
+                gateway_epoch_state,
+                cycle_number,
+                epoch,
+                time_interval,
+                &state_store,
+                &metrics,
+                max_block_range,
+                &cycle_block_hashes,
+                reorg_depth,
+            )
+            .await;
+
+            let Err(err) = result else {
+                break;
+            };
+
+            attempt += 1;
+            if attempt >= MAX_RETRY_ON_PROVIDER_ERROR {
+                error!(
+                    "Giving up on gateway epoch state for cycle {} after {} attempts: {:?}",
+                    cycle_number, attempt, err
+                );
+                break;
+            }
+            let backoff = Duration::from_secs(2u64.pow(attempt as u32));
+            warn!(
+                "Failed to generate gateway epoch state for cycle {} (attempt {}/{}): {:?}, retrying in {:?}",
+                cycle_number, attempt, MAX_RETRY_ON_PROVIDER_ERROR, err, backoff
+            );
+            time::sleep(backoff).await;
+        }
+
+        prune_old_cycle_states(
             gateway_epoch_state,
             cycle_number,
-            epoch,
-            time_interval,
+            &state_store,
+            gateway_block_states_to_maintain,
         )
-        .await
-        .unwrap();
-
-        prune_old_cycle_states(gateway_epoch_state, cycle_number).await;
+        .await;
 
         cycle_number += 1;
     }
 }
 
-// TODO: if fails, add a retry mechanism
 pub async fn generate_gateway_epoch_state_for_cycle(
     contract_address: Address,
     provider: &Provider<Http>,
@@ -105,7 +325,32 @@ pub async fn generate_gateway_epoch_state_for_cycle(
     cycle_number: u64,
     epoch: u64,
     time_interval: u64,
+    state_store: &Arc<dyn StateStore>,
+    metrics: &Arc<GatewayStateMetrics>,
+    max_block_range: u64,
+    cycle_block_hashes: &Arc<RwLock<BTreeMap<u64, CycleBlockHash>>>,
+    reorg_depth: u64,
 ) -> Result<()> {
+    repair_reorg(
+        provider,
+        gateway_epoch_state,
+        cycle_block_hashes,
+        state_store,
+        reorg_depth,
+    )
+    .await?;
+
+    // A cycle only counts as done once its state has actually been
+    // persisted by `state_store.put_cycle` below, not merely because its
+    // key exists in `gateway_epoch_state`: that key is inserted as a
+    // staging copy of the previous cycle's state before the fetch further
+    // down ever runs, so guarding on map-key presence let a cycle whose
+    // `fetch_logs_with_retry` failed get silently treated as complete the
+    // next time the caller's retry loop called in here for it.
+    if let Ok(Some(_)) = state_store.get_cycle(cycle_number).await {
+        return Ok(());
+    }
+
     let mut last_added_cycle: Option<u64> = None;
     let added_cycles: Vec<u64>;
     // scope for the read lock
@@ -114,9 +359,7 @@ pub async fn generate_gateway_epoch_state_for_cycle(
         added_cycles = gateway_epoch_state_guard.keys().cloned().collect();
     }
     for cycle in added_cycles.iter().rev() {
-        if *cycle == cycle_number {
-            return Ok(());
-        } else if *cycle < cycle_number {
+        if *cycle < cycle_number {
             last_added_cycle = Some(cycle.clone());
         }
     }
@@ -162,6 +405,17 @@ pub async fn generate_gateway_epoch_state_for_cycle(
 
     let to_block_number = to_block_number.unwrap();
 
+    // remember the canonical block hash at this cycle's boundary so a later
+    // reorg can be detected by comparing it against the chain
+    record_cycle_block_hash(
+        provider,
+        cycle_block_hashes,
+        state_store,
+        cycle_number,
+        to_block_number,
+    )
+    .await?;
+
     if last_added_cycle.is_none() {
         // initialize the gateway epoch state[current_cycle] with empty map
         // scope for the write lock
@@ -209,24 +463,25 @@ pub async fn generate_gateway_epoch_state_for_cycle(
         }
     }
 
-    let event_filter = Filter::new()
-        .address(contract_address)
-        .from_block(from_block_number)
-        .to_block(to_block_number)
-        .topic0(vec![
-            keccak256("GatewayRegistered(bytes,address,address,uint256,uint256[])"),
-            keccak256("GatewayDeregistered(bytes)"),
-            keccak256("GatewayStakeAdded(bytes,uint256,uint256)"),
-            keccak256("GatewayStakeRemoved(bytes,uint256,uint256)"),
-            keccak256("ChainAdded(bytes,uint256)"),
-            keccak256("ChainRemoved(bytes,uint256)"),
-        ]);
-
-    let logs = provider
-        .get_logs(&event_filter)
-        .await
-        .context("Failed to get logs for the gateway contract")
-        .unwrap();
+    let topics = vec![
+        keccak256("GatewayRegistered(bytes,address,address,uint256,uint256[])"),
+        keccak256("GatewayDeregistered(bytes)"),
+        keccak256("GatewayStakeAdded(bytes,uint256,uint256)"),
+        keccak256("GatewayStakeRemoved(bytes,uint256,uint256)"),
+        keccak256("ChainAdded(bytes,uint256)"),
+        keccak256("ChainRemoved(bytes,uint256)"),
+    ];
+
+    let logs = fetch_logs_with_retry(
+        provider,
+        contract_address,
+        topics,
+        from_block_number,
+        to_block_number,
+        max_block_range,
+        metrics,
+    )
+    .await?;
 
     for log in logs {
         let topics = log.topics.clone();
@@ -234,6 +489,10 @@ pub async fn generate_gateway_epoch_state_for_cycle(
         if topics[0]
             == keccak256("GatewayRegistered(bytes,address,address,uint256,uint256[])").into()
         {
+            metrics
+                .events_processed
+                .with_label_values(&["GatewayRegistered"])
+                .inc();
             process_gateway_registered_event(
                 log,
                 cycle_number,
@@ -242,26 +501,77 @@ pub async fn generate_gateway_epoch_state_for_cycle(
             )
             .await;
         } else if topics[0] == keccak256("GatewayDeregistered(bytes)").into() {
+            metrics
+                .events_processed
+                .with_label_values(&["GatewayDeregistered"])
+                .inc();
             process_gateway_deregistered_event(log, to_block_number, &gateway_epoch_state).await;
         } else if topics[0] == keccak256("GatewayStakeAdded(bytes,uint256,uint256)").into() {
+            metrics
+                .events_processed
+                .with_label_values(&["GatewayStakeAdded"])
+                .inc();
             process_gateway_stake_added_event(log, cycle_number, &gateway_epoch_state).await;
         } else if topics[0] == keccak256("GatewayStakeRemoved(bytes,uint256,uint256)").into() {
+            metrics
+                .events_processed
+                .with_label_values(&["GatewayStakeRemoved"])
+                .inc();
             process_gateway_stake_removed_event(log, cycle_number, &gateway_epoch_state).await;
         } else if topics[0] == keccak256("ChainAdded(bytes,uint256)").into() {
+            metrics
+                .events_processed
+                .with_label_values(&["ChainAdded"])
+                .inc();
             process_chain_added_event(log, cycle_number, &gateway_epoch_state).await;
         } else if topics[0] == keccak256("ChainRemoved(bytes,uint256)").into() {
+            metrics
+                .events_processed
+                .with_label_values(&["ChainRemoved"])
+                .inc();
             process_chain_removed_event(log, cycle_number, &gateway_epoch_state).await;
         }
     }
 
     // TODO: fetch the gateways mapping for the updated stakes.
 
+    // persist the cycle we just computed so a restart can resume from it
+    // instead of re-scanning from block 0
+    let cycle_state = gateway_epoch_state
+        .read()
+        .await
+        .get(&cycle_number)
+        .cloned()
+        .unwrap_or_default();
+    if let Err(err) = state_store.put_cycle(cycle_number, &cycle_state).await {
+        error!(
+            "Failed to persist gateway epoch state for cycle {}: {:?}",
+            cycle_number, err
+        );
+    }
+
+    metrics.current_cycle_number.set(cycle_number as i64);
+    metrics.current_cycle_to_block.set(to_block_number as i64);
+    metrics.active_gateways.set(cycle_state.len() as i64);
+    let total_stake: u128 = cycle_state
+        .values()
+        .map(|gateway_data| gateway_data.stake_amount.as_u128())
+        .sum();
+    // total_stake is in base units, where a single MIN_GATEWAY_STAKE already
+    // exceeds i64::MAX; scale down by GATEWAY_STAKE_ADJUSTMENT_FACTOR to
+    // whole tokens before handing it to the (f64-backed) gauge.
+    let total_stake_scaled =
+        total_stake as f64 / GATEWAY_STAKE_ADJUSTMENT_FACTOR.as_u128() as f64;
+    metrics.total_stake_amount.set(total_stake_scaled);
+
     Ok(())
 }
 
 async fn prune_old_cycle_states(
     gateway_epoch_state: &Arc<RwLock<BTreeMap<u64, BTreeMap<Bytes, GatewayData>>>>,
     current_cycle: u64,
+    state_store: &Arc<dyn StateStore>,
+    gateway_block_states_to_maintain: u64,
 ) {
     let mut cycles_to_remove = vec![];
 
@@ -272,7 +582,7 @@ async fn prune_old_cycle_states(
             // if a state is older than 1.5 times the number of states to maintain, remove it
             // chosen a number larger than the number to maintain because in some cases, of delay,
             // an older state might be used to read and initialize the current state
-            if current_cycle - cycle >= (GATEWAY_BLOCK_STATES_TO_MAINTAIN * 3 / 2) {
+            if current_cycle - cycle >= (gateway_block_states_to_maintain * 3 / 2) {
                 cycles_to_remove.push(cycle.clone());
             } else {
                 break;
@@ -282,8 +592,14 @@ async fn prune_old_cycle_states(
     // scope for the write lock
     {
         let mut gateway_epoch_state_guard = gateway_epoch_state.write().await;
-        for cycle in cycles_to_remove {
-            gateway_epoch_state_guard.remove(&cycle);
+        for cycle in &cycles_to_remove {
+            gateway_epoch_state_guard.remove(cycle);
+        }
+    }
+
+    if let Some(oldest_kept) = cycles_to_remove.iter().max().map(|cycle| cycle + 1) {
+        if let Err(err) = state_store.prune(oldest_kept).await {
+            error!("Failed to prune persisted gateway epoch state: {:?}", err);
         }
     }
 }
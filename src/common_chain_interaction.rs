@@ -1,38 +1,99 @@
-use anyhow::{anyhow, Context, Result};
-use async_recursion::async_recursion;
-use ethers::abi::{decode, Address, FixedBytes, ParamType};
+use anyhow::{Context, Result};
+use ethers::abi::{decode, Address, Error as AbiError, FixedBytes, ParamType};
 use ethers::prelude::*;
 use ethers::providers::Provider;
 use ethers::utils::keccak256;
 use k256::ecdsa::SigningKey;
-use log::{error, info};
-use rand::rngs::StdRng;
-use rand::{Rng, SeedableRng};
+use log::{error, info, warn};
 use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc::{channel, Receiver, Sender};
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 use tokio::{task, time};
+use tracing::Instrument;
 
 use crate::chain_util::{
     pub_key_to_address, sign_job_response_response, sign_reassign_gateway_relay_response,
     sign_relay_job_response,
 };
 use crate::common_chain_gateway_state_service::GatewayData;
+use crate::confirmation_buffer::ConfirmationBuffer;
 use crate::constant::{
-    MAX_GATEWAY_RETRIES, OFFEST_FOR_GATEWAY_EPOCH_STATE_CYCLE, REQUEST_RELAY_TIMEOUT,
-    RESPONSE_RELAY_TIMEOUT,
+    COMMON_CHAIN_CONFIRMATION_DEPTH, GAS_WALLET_ROTATION_POLL_INTERVAL,
+    GATEWAY_CLIENT_METRICS_ADDR, GATEWAY_EPOCH_STATE_NOTIFY_TIMEOUT, LEASE_HEARTBEAT_INTERVAL,
+    LEASE_TTL, MAX_GATEWAY_RETRIES, OFFEST_FOR_GATEWAY_EPOCH_STATE_CYCLE,
+    RELAY_FINALITY_CONFIRMATION_DEPTH, RELAY_FINALITY_TIMEOUT, REQUEST_RELAY_TIMEOUT,
+    RPC_FAILOVER_RECONNECT_BACKOFF, STALE_BLOCK_TIMEOUT,
 };
 use crate::contract_abi::{
     CommonChainGatewayContract, CommonChainJobsContract, RequestChainContract,
 };
+use crate::dead_letter::{DeadLetterEntry, DeadLetterStore};
+use crate::error::GatewayError;
+use crate::eventuality::{Completion, Eventuality, EventualityTracker};
+use crate::gas_oracle::{GasOracle, GasOracleConfig};
+use crate::gas_wallet::GasWallet;
+use crate::gateway_selector::GatewaySelector;
+use crate::job_store::JobStore;
+use crate::metrics::{serve_client_metrics, GatewayClientMetrics};
 use crate::model::{
     ComChainJobType, CommonChainClient, Job, JobResponse, ReqChainJobType, RequestChainClient,
     RequestChainData,
 };
+use crate::nonce_manager::{is_nonce_error, NonceManager};
+use crate::poll_timer::{with_timeout, WithPollTimer, RPC_TIMEOUT};
+use crate::provider_stack::ProviderStack;
+use crate::retry_policy::RetryPolicyTable;
+use crate::rpc_failover::FailoverEndpoints;
+use crate::txn_manager::TxnManager;
 use crate::HttpProvider;
+use uuid::Uuid;
+
+/// Namespace for deriving a stable correlation id from a `job_id`, so every
+/// span touching the same job across `job_responded_handler` ->
+/// `txns_to_request_chain` -> `job_response_txn` carries an identical id to
+/// grep for, without threading extra state through the channel hops between
+/// them.
+const JOB_CORRELATION_NAMESPACE: Uuid = Uuid::from_bytes([
+    0x6b, 0x61, 0x70, 0x69, 0x6c, 0x2d, 0x6a, 0x6f, 0x62, 0x2d, 0x63, 0x6f, 0x72, 0x72, 0x2d, 0x31,
+]);
+
+fn job_response_correlation_id(job_id: U256) -> Uuid {
+    Uuid::new_v5(&JOB_CORRELATION_NAMESPACE, job_id.to_string().as_bytes())
+}
+
+/// Connect a WebSocket provider against `failover`'s current endpoint,
+/// recording a failure and rotating to the next endpoint (per
+/// `FailoverEndpoints::record_failure`) on every connect error instead of
+/// unwrapping, so a down provider fails over rather than taking the whole
+/// subscription task with it. Used by both the log- and block-subscription
+/// tasks spawned from `handle_all_req_chain_events`.
+async fn connect_req_chain_ws(failover: &FailoverEndpoints, chain_id: u64) -> Provider<Ws> {
+    loop {
+        match Provider::<Ws>::connect_with_reconnects(failover.current_url(), 5).await {
+            Ok(client) => return client,
+            Err(err) => {
+                error!(
+                    "Failed to connect to request chain {} websocket provider at {}: {:?}",
+                    chain_id,
+                    failover.current_url(),
+                    err
+                );
+                failover.record_failure().await;
+                time::sleep(RPC_FAILOVER_RECONNECT_BACKOFF).await;
+            }
+        }
+    }
+}
+
+/// Emitted by `com_chain_jobs_contract.relay_job` on the Common Chain.
+/// `RelayFinalityEventuality` checks for this log's presence in a relay
+/// transaction's receipt, the same "the event also exists alongside the
+/// transfer" invariant used for Serai's InInstructions, rather than trusting
+/// a non-reverted receipt alone.
+const COMMON_CHAIN_JOB_RELAYED_EVENT: &str = "JobRelayed(uint256,uint256,address,uint8)";
 
 impl CommonChainClient {
     pub async fn new(
@@ -47,6 +108,12 @@ impl CommonChainClient {
         request_chain_list: Vec<RequestChainData>,
         epoch: u64,
         time_interval: u64,
+        job_store: Arc<dyn JobStore>,
+        dead_letter_store: Arc<dyn DeadLetterStore>,
+        retry_policy: Arc<RetryPolicyTable>,
+        gateway_selector: Arc<dyn GatewaySelector>,
+        gas_oracle: Arc<GasOracle>,
+        metrics: Arc<GatewayClientMetrics>,
     ) -> Self {
         info!("Initializing Common Chain Client...");
         let gateway_contract = CommonChainGatewayContract::new(
@@ -66,30 +133,157 @@ impl CommonChainClient {
             )
             .unwrap();
 
-        CommonChainClient {
+        let address = pub_key_to_address(&enclave_pub_key).unwrap();
+        let gas_wallet = GasWallet::new(
+            com_chain_jobs_contract.client().as_ref(),
             signer,
+            gas_oracle,
+        )
+        .await
+        .context("failed to initialize gas wallet for the Common Chain")
+        .unwrap();
+
+        let (gateway_epoch_state_ready, _) = watch::channel(0u64);
+
+        CommonChainClient {
             enclave_signer_key,
-            address: pub_key_to_address(&enclave_pub_key).unwrap(),
+            address,
             chain_ws_client,
             contract_addr: *contract_addr,
             gateway_contract_addr: *gateway_contract_addr,
             gateway_contract,
             com_chain_jobs_contract,
+            gas_wallet: Arc::new(tokio::sync::RwLock::new(gas_wallet)),
+            gas_wallet_rotation_barrier: Arc::new(tokio::sync::RwLock::new(())),
             req_chain_clients: HashMap::new(),
             gateway_epoch_state,
+            gateway_epoch_state_ready,
             request_chain_list,
-            // TODO: can the job_id be same ever? Should it be used as the key?
-            active_jobs: Arc::new(RwLock::new(HashMap::new())),
+            runner_id: Uuid::new_v4().to_string(),
             epoch,
             time_interval,
+            job_store,
+            dead_letter_store,
+            retry_policy,
+            gateway_selector,
+            eventuality_tracker: Arc::new(EventualityTracker::new()),
+            com_chain_confirmation_buffer: Arc::new(RwLock::new(ConfirmationBuffer::new(
+                COMMON_CHAIN_CONFIRMATION_DEPTH,
+            ))),
+            metrics,
+        }
+    }
+
+    /// Install `new_signer` as the Common Chain gas-paying key, in place of
+    /// restarting and re-registering to change it. The enclave signing key
+    /// and this gateway's registered `address` are untouched — only the key
+    /// that pays for `gateway_contract`/`com_chain_jobs_contract`
+    /// submissions changes.
+    ///
+    /// Takes a write lock on `gas_wallet_rotation_barrier` for the whole
+    /// swap, which blocks `relay_job_txn`/`reassign_gateway_relay_txn` from
+    /// starting a new submission (they hold a read lock for the duration of
+    /// theirs) without interrupting ones already in flight under the old
+    /// key. Before swapping, it waits for every relay this gateway already
+    /// submitted (a job with a recorded relay submission) to clear, so a
+    /// submission the old key already sent has a chance to reach finality
+    /// or be cleared for rebroadcast, and any relay/reassign still outstanding gets
+    /// picked up fresh under the new key once the swap completes, instead
+    /// of being signed with a nonce baseline that belongs to the old
+    /// address.
+    pub async fn rotate_gas_wallet(self: Arc<Self>, new_signer: LocalWallet) -> Result<(), GatewayError> {
+        tracing::info!(new_address = ?new_signer.address(), "rotating Common Chain gas wallet");
+
+        let _rotation_guard = self.gas_wallet_rotation_barrier.write().await;
+
+        self.wait_for_in_flight_jobs_to_settle().await?;
+
+        let gas_oracle = Arc::clone(&self.gas_wallet.read().await.provider_stack.gas_oracle);
+        let new_wallet = GasWallet::new(
+            self.com_chain_jobs_contract.client().as_ref(),
+            new_signer,
+            gas_oracle,
+        )
+        .await
+        .map_err(GatewayError::Provider)?;
+
+        let new_address = new_wallet.address;
+        *self.gas_wallet.write().await = new_wallet;
+
+        tracing::info!(new_address = ?new_address, "Common Chain gas wallet rotation complete");
+        Ok(())
+    }
+
+    /// Poll the job store until no job this gateway has already relayed
+    /// under the outgoing key is still awaiting confirmation, so a
+    /// rotation doesn't swap out the signer a submission already on the
+    /// wire was made with, mid-confirmation. Deliberately narrower than
+    /// "every active job": `gas_wallet_rotation_barrier`'s write lock
+    /// already excludes any submission actually in progress the instant
+    /// it's acquired above, and `job_placed_handler` keeps accepting and
+    /// persisting new jobs throughout a rotation (it never touches the
+    /// barrier), so waiting on `list_active()` as a whole would never
+    /// converge under live traffic.
+    async fn wait_for_in_flight_jobs_to_settle(&self) -> Result<(), GatewayError> {
+        loop {
+            let active = self
+                .job_store
+                .list_active()
+                .await
+                .map_err(GatewayError::JobStore)?;
+
+            let mut in_flight = 0usize;
+            for job in &active {
+                if job.gateway_address != Some(self.address) {
+                    continue;
+                }
+                if self
+                    .job_store
+                    .relay_submission(job.job_id)
+                    .await
+                    .map_err(GatewayError::JobStore)?
+                    .is_some()
+                {
+                    in_flight += 1;
+                }
+            }
+
+            if in_flight == 0 {
+                return Ok(());
+            }
+            tracing::info!(
+                in_flight,
+                "gas wallet rotation waiting for in-flight relay submissions to settle under the outgoing key"
+            );
+            time::sleep(GAS_WALLET_ROTATION_POLL_INTERVAL).await;
         }
     }
 
     pub async fn run(self: Arc<Self>) -> Result<(), Box<dyn Error>> {
+        // Single poller driving every tracked relay-slash and
+        // response-slash eventuality, instead of one sleep task per job.
+        task::spawn(Arc::clone(&self.eventuality_tracker).run());
+
+        // Serve relay latency / selection latency / slashing-rate metrics
+        // for operators to alert on.
+        let metrics = (*self.metrics).clone();
+        let metrics_addr = GATEWAY_CLIENT_METRICS_ADDR
+            .parse()
+            .context("invalid GATEWAY_CLIENT_METRICS_ADDR")?;
+        task::spawn(serve_client_metrics(metrics_addr, metrics));
+
         // setup for the listening events on Request Chain and calling Common Chain functions
         let (req_chain_tx, com_chain_rx) = channel::<(Job, Arc<CommonChainClient>)>(100);
         let self_clone = Arc::clone(&self);
-        self_clone.txns_to_common_chain(com_chain_rx).await?;
+        self_clone
+            .txns_to_common_chain(com_chain_rx, req_chain_tx.clone())
+            .await?;
+
+        // Rehydrate jobs that were still in flight before a restart and
+        // resume tracking their eventualities, instead of starting every
+        // job fresh and orphaning the old ones.
+        self.clone().rehydrate_active_jobs(req_chain_tx.clone()).await;
+
         let self_clone = Arc::clone(&self);
         self_clone.handle_all_req_chain_events(req_chain_tx).await?;
 
@@ -97,10 +291,118 @@ impl CommonChainClient {
         let (com_chain_tx, req_chain_rx) = channel::<(JobResponse, Arc<CommonChainClient>)>(100);
         let self_clone = Arc::clone(&self);
         self_clone.txns_to_request_chain(req_chain_rx).await?;
+
+        // Reclaims jobs whose response lease went stale well before the
+        // RetryPolicy-governed response slash deadline, instead of waiting
+        // for it to notice a dead gateway.
+        let self_clone = Arc::clone(&self);
+        let sweeper_tx = com_chain_tx.clone();
+        task::spawn(async move { self_clone.response_lease_sweeper(sweeper_tx).await });
+
         self.handle_all_com_chain_events(com_chain_tx).await?;
         Ok(())
     }
 
+    /// Replay every job still outstanding in the job store: resume relay
+    /// finality tracking for jobs this gateway itself relayed (if a
+    /// submission was recorded before the restart), or resume the
+    /// relay-slash eventuality for jobs another gateway is relaying.
+    async fn rehydrate_active_jobs(self: Arc<Self>, tx: Sender<(Job, Arc<CommonChainClient>)>) {
+        let stored_jobs = match self.job_store.list_active().await {
+            Ok(jobs) => jobs,
+            Err(err) => {
+                error!("Failed to rehydrate active jobs from the job store: {:?}", err);
+                return;
+            }
+        };
+
+        for job in stored_jobs {
+            if job.gateway_address == Some(self.address) {
+                let submission = match self.job_store.relay_submission(job.job_id).await {
+                    Ok(submission) => submission,
+                    Err(err) => {
+                        error!(
+                            "Failed to look up relay submission for job ID {:?}: {:?}",
+                            job.job_id, err
+                        );
+                        continue;
+                    }
+                };
+
+                let Some(submission) = submission else {
+                    info!(
+                        "Rehydrated job ID: {:?}, this gateway is assigned to relay/respond to it; no relay submitted yet",
+                        job.job_id
+                    );
+                    continue;
+                };
+
+                info!(
+                    "Rehydrated job ID: {:?}, resuming relay finality tracking for transaction {:?}",
+                    job.job_id, submission.tx_hash
+                );
+                let deadline = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs()
+                    + RELAY_FINALITY_TIMEOUT.as_secs();
+                self.eventuality_tracker
+                    .track(Box::new(RelayFinalityEventuality {
+                        client: self.clone(),
+                        job,
+                        tx_hash: submission.tx_hash,
+                        tx: tx.clone(),
+                        deadline,
+                    }))
+                    .await;
+                continue;
+            }
+
+            info!(
+                "Rehydrated job ID: {:?}, resuming relay-slash eventuality",
+                job.job_id
+            );
+            // The exact deadline isn't persisted, so this restarts the
+            // relay-slash window from now instead of where it left off.
+            let deadline = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                + REQUEST_RELAY_TIMEOUT;
+            self.eventuality_tracker
+                .track(Box::new(JobRelayEventuality {
+                    client: self.clone(),
+                    job,
+                    tx: tx.clone(),
+                    deadline,
+                }))
+                .await;
+        }
+    }
+
+    /// Mirror `failover`'s active endpoint and its consecutive failure
+    /// count into Prometheus gauges, so operators can see which RPC is
+    /// serving a request chain's subscriptions without reading logs.
+    async fn record_req_chain_ws_health(&self, chain_id: u64, failover: &FailoverEndpoints) {
+        let active_index = failover.active_index();
+        let consecutive_failures = failover
+            .health_snapshot()
+            .await
+            .get(active_index)
+            .map(|(_, health)| health.consecutive_failures)
+            .unwrap_or_default();
+
+        let chain_id = chain_id.to_string();
+        self.metrics
+            .req_chain_active_ws_endpoint
+            .with_label_values(&[&chain_id])
+            .set(active_index as i64);
+        self.metrics
+            .req_chain_ws_endpoint_consecutive_failures
+            .with_label_values(&[&chain_id])
+            .set(consecutive_failures as i64);
+    }
+
     async fn handle_all_req_chain_events(
         self: Arc<Self>,
         tx: Sender<(Job, Arc<CommonChainClient>)>,
@@ -108,14 +410,29 @@ impl CommonChainClient {
         info!("Initializing Request Chain Clients for all request chains...");
         let mut req_chain_data = self.request_chain_list.clone();
         let mut request_chain_clients: HashMap<String, Arc<RequestChainClient>> = HashMap::new();
+        // Kept alongside `request_chain_clients` (rather than read back out
+        // of it) since the latter is moved into `self.req_chain_clients`
+        // below, before the per-chain subscription tasks are spawned.
+        let mut ws_rpc_failovers: HashMap<u64, Arc<FailoverEndpoints>> = HashMap::new();
+        let gas_signer = self.gas_wallet.read().await.signer.clone();
         for request_chain in req_chain_data.clone() {
-            let signer = self.signer.clone().with_chain_id(request_chain.chain_id);
+            let signer = gas_signer.clone().with_chain_id(request_chain.chain_id);
             let signer_address = signer.address();
 
-            let req_chain_http_client = Provider::<Http>::connect(&request_chain.rpc_url)
-                .await
-                .with_signer(signer)
-                .nonce_manager(signer_address);
+            // `Provider::<Http>::connect` doesn't make a network call or
+            // return a `Result`, so there's no connect-time failure to fail
+            // over on; the first configured endpoint is used directly and the
+            // rest of `http_rpc_urls` is not used. Unlike the WS side, the
+            // HTTP endpoints get no `FailoverEndpoints` and no health tracking.
+            let req_chain_http_client = Provider::<Http>::connect(
+                request_chain
+                    .http_rpc_urls
+                    .first()
+                    .context("http_rpc_urls must not be empty")?,
+            )
+            .await
+            .with_signer(signer)
+            .nonce_manager(signer_address);
             info!(
                 "Connected to the request chain provider for chain_id: {}",
                 request_chain.chain_id
@@ -124,11 +441,34 @@ impl CommonChainClient {
                 request_chain.contract_address,
                 Arc::new(req_chain_http_client),
             );
+            // Independent of the Common Chain's nonce manager: nonces don't
+            // carry across chains, so each request chain's signer address
+            // gets its own counter.
+            let nonce_manager = Arc::new(
+                NonceManager::new(contract.client().as_ref(), signer_address)
+                    .await
+                    .context(format!(
+                        "failed to initialize nonce manager for request chain: {}",
+                        request_chain.chain_id
+                    ))?,
+            );
+            let gas_oracle = Arc::new(GasOracle::new(GasOracleConfig {
+                multiplier: request_chain.gas_multiplier,
+                priority_fee_floor: request_chain.priority_fee_floor,
+                max_fee_cap: request_chain.max_fee_cap,
+            }));
+            let provider_stack = Arc::new(ProviderStack::new(gas_oracle, nonce_manager));
+            let ws_rpc_failover = Arc::new(
+                FailoverEndpoints::new(request_chain.ws_rpc_urls.clone())
+                    .context("invalid ws_rpc_urls for request chain")?,
+            );
+            ws_rpc_failovers.insert(request_chain.chain_id, Arc::clone(&ws_rpc_failover));
             let req_chain_client = Arc::from(RequestChainClient {
                 chain_id: request_chain.chain_id,
                 contract_address: request_chain.contract_address,
-                rpc_url: request_chain.rpc_url,
+                ws_rpc_failover,
                 contract,
+                provider_stack,
             });
             request_chain_clients.insert(request_chain.chain_id.to_string(), req_chain_client);
         }
@@ -151,80 +491,155 @@ impl CommonChainClient {
                 request_chain.chain_id
             );
 
+            // Logs are held here, keyed by block number, until they're
+            // buried under `request_chain.confirmation_depth` confirmations;
+            // only then are they dispatched to a handler. Shared between the
+            // log subscriber below and the block-header subscriber that
+            // drains it.
+            let confirmation_buffer = Arc::new(RwLock::new(ConfirmationBuffer::new(
+                request_chain.confirmation_depth,
+            )));
+            let ws_rpc_failover = ws_rpc_failovers
+                .get(&request_chain.chain_id)
+                .cloned()
+                .expect("ws_rpc_failover was just inserted for this chain above");
+
             let self_clone = Arc::clone(&self);
             let tx_clone = tx.clone();
-            let req_chain_ws_client =
-                Provider::<Ws>::connect_with_reconnects(request_chain.rpc_url.clone(), 5).await.context(
-                    "Failed to connect to the request chain websocket provider. Please check the chain url.",
-                )?;
-            // Spawn a new task for each Request Chain Contract
+            let request_chain_clone = request_chain.clone();
+            let confirmation_buffer_clone = Arc::clone(&confirmation_buffer);
+            let log_failover = Arc::clone(&ws_rpc_failover);
+            let event_filter_clone = event_filter.clone();
+            // Spawn a new task for each Request Chain Contract. Reconnects
+            // and re-subscribes against the next healthy endpoint in
+            // `ws_rpc_failover` on a transport error or a dropped
+            // subscription instead of unwrapping, so a single flaky
+            // provider doesn't take this chain's event intake offline.
             task::spawn(async move {
-                // register subscription
-                let mut stream = req_chain_ws_client
-                    .subscribe_logs(&event_filter)
-                    .await
-                    .context(format!(
-                        "failed to subscribe to events on Request Chain: {}",
-                        request_chain.chain_id
-                    ))
-                    .unwrap();
-
-                while let Some(log) = stream.next().await {
-                    let topics = log.topics.clone();
-
-                    if topics[0]
-                    == keccak256(
-                        "JobRelayed(uint256,bytes32,bytes,uint256,uint256,uint256,uint256,uint256)",
-                    )
-                    .into()
-                {
-                    info!(
-                        "Request Chain ID: {:?}, JobPlace jobID: {:?}",
-                        request_chain.chain_id, log.topics[1]
-                    );
-                    let self_clone = Arc::clone(&self_clone);
-                    let tx = tx_clone.clone();
-                    task::spawn(async move {
-                        let job = self_clone.clone()
-                            .get_job_from_job_relay_event(
-                                log,
-                                 0 as u8,
-                                  &request_chain.chain_id.to_string()
-                            )
-                            .await
-                            .context("Failed to decode event")
-                            .unwrap();
-                        self_clone.job_placed_handler(
-                                &request_chain.chain_id.to_string(),
-                                job,
-                                tx.clone(),
-                            )
-                            .await;
-                    });
-                } else if topics[0] == keccak256("JobCancelled(uint256)").into() {
-                    info!(
-                        "Request Chain ID: {:?}, JobCancelled jobID: {:?}",
-                        request_chain.chain_id, log.topics[1]
-                    );
-                    let self_clone = Arc::clone(&self_clone);
-                    task::spawn(async move {
-                        self_clone.cancel_job_with_job_id(U256::from_big_endian(log.topics[1].as_fixed_bytes())).await;
-                    });
-                } else if topics[0] == keccak256("GatewayReassigned(uint256,uint256,address,address,uint8)").into() {
-                    info!(
-                        "Request Chain ID: {:?}, GatewayReassigned jobID: {:?}",
-                        request_chain.chain_id, log.topics[1]
-                    );
-                    let self_clone = Arc::clone(&self_clone);
-                    task::spawn(async move {
-                        self_clone.gateway_reassigned_handler(log).await;
-                    });
-                } else {
-                    error!(
-                        "Request Chain ID: {:?}, Unknown event: {:?}",
-                        request_chain.chain_id, log
+                loop {
+                    let ws_client =
+                        connect_req_chain_ws(&log_failover, request_chain_clone.chain_id).await;
+                    let mut stream = match ws_client.subscribe_logs(&event_filter_clone).await {
+                        Ok(stream) => stream,
+                        Err(err) => {
+                            error!(
+                                "Failed to subscribe to events on Request Chain {}: {:?}",
+                                request_chain_clone.chain_id, err
+                            );
+                            log_failover.record_failure().await;
+                            self_clone
+                                .record_req_chain_ws_health(request_chain_clone.chain_id, &log_failover)
+                                .await;
+                            time::sleep(RPC_FAILOVER_RECONNECT_BACKOFF).await;
+                            continue;
+                        }
+                    };
+
+                    while let Some(log) = stream.next().await {
+                        if let Some(block_number) = log.block_number {
+                            log_failover.record_success(block_number.as_u64()).await;
+                            self_clone
+                                .record_req_chain_ws_health(request_chain_clone.chain_id, &log_failover)
+                                .await;
+                        }
+                        let orphaned = confirmation_buffer_clone.write().await.ingest(log);
+                        for orphaned_log in orphaned {
+                            Arc::clone(&self_clone)
+                                .compensate_orphaned_req_chain_log(request_chain_clone.clone(), orphaned_log)
+                                .await;
+                        }
+                    }
+
+                    warn!(
+                        "Request Chain {} log subscription ended; reconnecting",
+                        request_chain_clone.chain_id
                     );
+                    log_failover.record_failure().await;
+                    self_clone
+                        .record_req_chain_ws_health(request_chain_clone.chain_id, &log_failover)
+                        .await;
                 }
+            });
+
+            let self_clone = Arc::clone(&self);
+            let tx_clone = tx_clone.clone();
+            let request_chain_clone = request_chain.clone();
+            let confirmation_buffer_clone = Arc::clone(&confirmation_buffer);
+            let block_failover = Arc::clone(&ws_rpc_failover);
+            // Drains the confirmation buffer on every new block, dispatching
+            // whichever pending logs just crossed `confirmation_depth`. Also
+            // the source of stale-block detection: if no block arrives
+            // within `STALE_BLOCK_TIMEOUT`, the active endpoint is treated
+            // as failed even though its connection never errored outright.
+            task::spawn(async move {
+                loop {
+                    let ws_client =
+                        connect_req_chain_ws(&block_failover, request_chain_clone.chain_id).await;
+                    let mut stream = match ws_client.subscribe_blocks().await {
+                        Ok(stream) => stream,
+                        Err(err) => {
+                            error!(
+                                "Failed to subscribe to new blocks on Request Chain {}: {:?}",
+                                request_chain_clone.chain_id, err
+                            );
+                            block_failover.record_failure().await;
+                            self_clone
+                                .record_req_chain_ws_health(request_chain_clone.chain_id, &block_failover)
+                                .await;
+                            time::sleep(RPC_FAILOVER_RECONNECT_BACKOFF).await;
+                            continue;
+                        }
+                    };
+
+                    loop {
+                        let block = match time::timeout(STALE_BLOCK_TIMEOUT, stream.next()).await {
+                            Ok(Some(block)) => block,
+                            Ok(None) => {
+                                warn!(
+                                    "Request Chain {} block subscription ended; reconnecting",
+                                    request_chain_clone.chain_id
+                                );
+                                block_failover.record_failure().await;
+                                self_clone
+                                    .record_req_chain_ws_health(request_chain_clone.chain_id, &block_failover)
+                                    .await;
+                                break;
+                            }
+                            Err(_) => {
+                                warn!(
+                                    "No new block on Request Chain {} within {:?}; treating endpoint as stale",
+                                    request_chain_clone.chain_id, STALE_BLOCK_TIMEOUT
+                                );
+                                block_failover.record_failure().await;
+                                self_clone
+                                    .record_req_chain_ws_health(request_chain_clone.chain_id, &block_failover)
+                                    .await;
+                                break;
+                            }
+                        };
+                        let Some(head_block_number) = block.number else {
+                            continue;
+                        };
+                        block_failover
+                            .record_success(head_block_number.as_u64())
+                            .await;
+                        self_clone
+                            .record_req_chain_ws_health(request_chain_clone.chain_id, &block_failover)
+                            .await;
+                        let confirmed_logs = confirmation_buffer_clone
+                            .write()
+                            .await
+                            .confirmed_logs(head_block_number.as_u64());
+                        for log in confirmed_logs {
+                            Arc::clone(&self_clone)
+                                .dispatch_req_chain_log(
+                                    request_chain_clone.clone(),
+                                    log,
+                                    tx_clone.clone(),
+                                )
+                                .await;
+                        }
+                    }
                 }
             });
         }
@@ -232,6 +647,97 @@ impl CommonChainClient {
         Ok(())
     }
 
+    /// Decode one confirmed Request Chain log and route it to the matching
+    /// handler. Split out of `handle_all_req_chain_events` so the
+    /// block-header subscriber that drains the `ConfirmationBuffer` can
+    /// reuse it.
+    async fn dispatch_req_chain_log(
+        self: Arc<Self>,
+        request_chain: RequestChainData,
+        log: Log,
+        tx: Sender<(Job, Arc<CommonChainClient>)>,
+    ) {
+        let topics = log.topics.clone();
+
+        if topics[0]
+            == keccak256(
+                "JobRelayed(uint256,bytes32,bytes,uint256,uint256,uint256,uint256,uint256)",
+            )
+            .into()
+        {
+            info!(
+                "Request Chain ID: {:?}, JobPlace jobID: {:?}",
+                request_chain.chain_id, log.topics[1]
+            );
+            task::spawn(async move {
+                let job = self
+                    .clone()
+                    .get_job_from_job_relay_event(log, 0 as u8, &request_chain.chain_id.to_string())
+                    .await
+                    .context("Failed to decode event")
+                    .unwrap();
+                self.job_placed_handler(&request_chain.chain_id.to_string(), job, tx.clone())
+                    .await;
+            });
+        } else if topics[0] == keccak256("JobCancelled(uint256)").into() {
+            info!(
+                "Request Chain ID: {:?}, JobCancelled jobID: {:?}",
+                request_chain.chain_id, log.topics[1]
+            );
+            task::spawn(async move {
+                self.cancel_job_with_job_id(U256::from_big_endian(log.topics[1].as_fixed_bytes()))
+                    .await;
+            });
+        } else if topics[0]
+            == keccak256("GatewayReassigned(uint256,uint256,address,address,uint8)").into()
+        {
+            info!(
+                "Request Chain ID: {:?}, GatewayReassigned jobID: {:?}",
+                request_chain.chain_id, log.topics[1]
+            );
+            task::spawn(async move {
+                self.gateway_reassigned_handler(log).await;
+            });
+        } else {
+            error!(
+                "Request Chain ID: {:?}, Unknown event: {:?}",
+                request_chain.chain_id, log
+            );
+        }
+    }
+
+    /// A log that was already confirmed-and-acted-on is contradicted by a
+    /// reorg. Only `JobRelayed` has a durable side effect worth undoing
+    /// (it assigns a gateway and may start tracking a relay-slash
+    /// eventuality); the other event types are re-emitted by the chain on
+    /// the canonical fork if they still apply, so they're just logged.
+    async fn compensate_orphaned_req_chain_log(self: Arc<Self>, request_chain: RequestChainData, log: Log) {
+        let topics = log.topics.clone();
+        if topics[0]
+            == keccak256(
+                "JobRelayed(uint256,bytes32,bytes,uint256,uint256,uint256,uint256,uint256)",
+            )
+            .into()
+        {
+            let job = self
+                .clone()
+                .get_job_from_job_relay_event(log, 0 as u8, &request_chain.chain_id.to_string())
+                .await
+                .context("Failed to decode orphaned event")
+                .unwrap();
+            warn!(
+                "Request Chain ID: {:?}, reorg orphaned JobRelayed jobID: {:?}; undoing relay assignment",
+                request_chain.chain_id, job.job_id
+            );
+            self.cancel_job_with_job_id(job.job_id).await;
+        } else {
+            warn!(
+                "Request Chain ID: {:?}, reorg orphaned event: {:?}",
+                request_chain.chain_id, log
+            );
+        }
+    }
+
     async fn get_job_from_job_relay_event(
         self: Arc<Self>,
         log: Log,
@@ -279,6 +785,15 @@ impl CommonChainClient {
         let mut job: Job = job.clone();
         let req_chain_client = self.req_chain_clients[req_chain_id].clone();
 
+        self.metrics
+            .jobs_received
+            .with_label_values(&[req_chain_id])
+            .inc();
+        self.metrics
+            .job_retry_number
+            .with_label_values(&[&job.retry_number.to_string()])
+            .inc();
+
         let gateway_address: Address;
 
         gateway_address = self
@@ -294,91 +809,43 @@ impl CommonChainClient {
 
         job.gateway_address = Some(gateway_address);
 
+        if let Err(err) = self.job_store.push(job.clone()).await {
+            error!("Failed to persist job ID: {:?}: {:?}", job.job_id, err);
+        }
+
         if gateway_address == self.address {
-            // scope for the write lock
-            {
-                self.active_jobs
-                    .write()
-                    .await
-                    .insert(job.job_id, job.clone());
-            }
             tx.send((job, self.clone())).await.unwrap();
         } else {
-            self.job_relayed_slash_timer(job.clone(), tx.clone())
-                .await
-                .unwrap();
+            let slash_deadline = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                + REQUEST_RELAY_TIMEOUT;
+            self.eventuality_tracker
+                .track(Box::new(JobRelayEventuality {
+                    client: self.clone(),
+                    job,
+                    tx,
+                    deadline: slash_deadline,
+                }))
+                .await;
         }
     }
 
-    #[async_recursion]
-    async fn job_relayed_slash_timer(
-        self: Arc<Self>,
-        mut job: Job,
-        tx: Sender<(Job, Arc<CommonChainClient>)>,
-    ) -> Result<()> {
-        time::sleep(Duration::from_secs(REQUEST_RELAY_TIMEOUT)).await;
-
-        let job_key = U256::from(keccak256(format!("{}-{}", job.job_id, job.req_chain_id)));
-        let onchain_job = self.com_chain_jobs_contract.jobs(job_key).await.unwrap();
-
-        let onchain_job: Job = Job {
-            job_id: onchain_job.0,
-            req_chain_id: onchain_job.1.as_u64(),
-            tx_hash: onchain_job.2.to_vec(),
-            code_input: onchain_job.3,
-            user_timout: onchain_job.4,
-            starttime: onchain_job.5,
-            max_gas_price: U256::zero(),
-            deposit: H160::zero(),
-            callback_deposit: U256::zero(),
-            job_owner: onchain_job.6,
-            job_type: ComChainJobType::JobRelay,
-            retry_number: onchain_job.9,
-            gateway_address: Some(onchain_job.7),
-        };
-
-        if onchain_job.job_id != job.job_id {
-            // TODO: confirm what to do in this case
-            error!("Job ID: {:?} not found in the contract", job.job_id);
-            return Err(anyhow!(
-                "Job ID: {:?} not found in the contract",
-                job.job_id
-            ));
-        }
-
-        if onchain_job.tx_hash != FixedBytes::default()
-            && onchain_job.code_input != Bytes::default()
-            && onchain_job.user_timout != U256::zero()
-            && onchain_job.starttime != U256::zero()
-            && onchain_job.req_chain_id != 0
-            && onchain_job.job_owner != H160::zero()
-            && onchain_job.gateway_address != Some(H160::zero())
-            && onchain_job.retry_number == job.retry_number
-        {
-            info!("Job ID: {:?}, JobRelayed event triggered", job.job_id);
-            return Ok(());
-        }
-
-        // slash the previous gateway
-        {
-            let self_clone = self.clone();
-            let mut job_clone = job.clone();
-            job_clone.job_type = ComChainJobType::SlashGatewayJob;
-            let tx_clone = tx.clone();
-            tx_clone.send((job_clone, self_clone)).await.unwrap();
-        }
-
-        job.retry_number += 1;
-        if job.retry_number >= MAX_GATEWAY_RETRIES {
-            info!("Job ID: {:?}, Max retries reached", job.job_id);
-            return Ok(());
-        }
-        job.gateway_address = None;
-
-        self.job_placed_handler(&job.req_chain_id.to_string(), job, tx)
-            .await;
-
-        Ok(())
+    /// Signal that `cycle` has been inserted into `gateway_epoch_state`,
+    /// waking any `select_gateway_for_job_id` call waiting on it. The
+    /// gateway-epoch-state service does not call this, so unless some other
+    /// caller does, a waiting selection re-checks the state only each time
+    /// `GATEWAY_EPOCH_STATE_NOTIFY_TIMEOUT` elapses.
+    pub fn notify_cycle_ready(&self, cycle: u64) {
+        self.gateway_epoch_state_ready.send_if_modified(|latest| {
+            if cycle > *latest {
+                *latest = cycle;
+                true
+            } else {
+                false
+            }
+        });
     }
 
     async fn select_gateway_for_job_id(
@@ -388,6 +855,7 @@ impl CommonChainClient {
         skips: u8,
         req_chain_client: Arc<RequestChainClient>,
     ) -> Result<Address> {
+        let selection_timer = self.metrics.gateway_selection_latency.start_timer();
         let current_cycle = (SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
@@ -396,6 +864,8 @@ impl CommonChainClient {
             - OFFEST_FOR_GATEWAY_EPOCH_STATE_CYCLE)
             / self.time_interval;
 
+        let epoch_state_wait_timer = self.metrics.gateway_epoch_state_wait_latency.start_timer();
+        let mut cycle_ready_rx = self.gateway_epoch_state_ready.subscribe();
         let all_gateways_data: Vec<GatewayData>;
         loop {
             let gateway_epoch_state_guard = self.gateway_epoch_state.read().await;
@@ -405,64 +875,41 @@ impl CommonChainClient {
             }
             drop(gateway_epoch_state_guard);
 
-            // wait for cycle to be created
-            time::sleep(Duration::from_secs(60)).await;
-        }
-
-        // create a weighted probability distribution for gateways based on stake amount
-        // For example, if there are 3 gateways with stake amounts 100, 200, 300
-        // then the distribution arrat will be [100, 300, 600]
-        let mut stake_distribution: Vec<u64> = vec![];
-        let mut total_stake: u64 = 0;
-        let mut gateway_data_of_req_chain: Vec<GatewayData> = vec![];
-        for gateway_data in all_gateways_data.iter() {
-            if gateway_data
-                .req_chain_ids
-                .contains(&U256::from(req_chain_client.chain_id))
-            {
-                gateway_data_of_req_chain.push(gateway_data.clone());
-                total_stake += gateway_data.stake_amount.as_u64();
-                stake_distribution.push(total_stake);
-            }
-        }
-
-        // random number between 1 to total_stake from the eed for the weighted random selection.
-        // use this seed in std_rng to generate a random number between 1 to total_stake
-        // skipping skips numbers from the random number generated
-        let mut rng = StdRng::seed_from_u64(seed);
-        for _ in 0..skips {
-            let _ = rng.gen_range(1..=total_stake);
+            // Wake as soon as the epoch-state service signals a new cycle,
+            // rather than polling on a fixed interval; bounded so a missed
+            // notification still falls back to re-checking periodically.
+            let _ = time::timeout(GATEWAY_EPOCH_STATE_NOTIFY_TIMEOUT, cycle_ready_rx.changed()).await;
         }
-        let random_number = rng.gen_range(1..=total_stake);
-
-        // select the gateway based on the random number
-        let res = stake_distribution.binary_search_by(|&probe| {
-            if probe < random_number {
-                std::cmp::Ordering::Less
-            } else {
-                std::cmp::Ordering::Greater
-            }
-        });
-        let index = match res {
-            Ok(index) => index,
-            Err(index) => index,
-        };
-        let selected_gateway = &gateway_data_of_req_chain[index];
-
-        info!(
-            "Job ID: {:?}, Gateway Address: {:?}",
-            job_id, selected_gateway.address
-        );
-
-        Ok(selected_gateway.address)
+        epoch_state_wait_timer.observe_duration();
+
+        let gateway = self
+            .gateway_selector
+            .select_gateway(
+                job_id,
+                seed,
+                skips,
+                all_gateways_data,
+                req_chain_client.chain_id,
+            )
+            .await;
+        selection_timer.observe_duration();
+        gateway
     }
 
     async fn cancel_job_with_job_id(self: Arc<Self>, job_id: U256) {
-        info!("Remove the job from the active jobs list");
+        info!("Remove the job from the job store");
+
+        let retry_number = match self.job_store.info(job_id).await {
+            Ok(Some(job)) => job.retry_number,
+            Ok(None) => return,
+            Err(err) => {
+                error!("Failed to look up job ID: {:?} in job store: {:?}", job_id, err);
+                return;
+            }
+        };
 
-        // scope for the write lock
-        {
-            self.active_jobs.write().await.remove(&job_id);
+        if let Err(err) = self.job_store.complete(job_id, retry_number).await {
+            error!("Failed to remove job ID: {:?} from job store: {:?}", job_id, err);
         }
     }
 
@@ -485,30 +932,33 @@ impl CommonChainClient {
             return;
         }
 
-        let job: Job;
-        // scope for the read lock
-        {
-            job = self.active_jobs.read().await.get(&job_id).unwrap().clone();
-        }
+        let job = match self.job_store.info(job_id).await {
+            Ok(Some(job)) => job,
+            Ok(None) => return,
+            Err(err) => {
+                error!("Failed to look up job ID: {:?} in job store: {:?}", job_id, err);
+                return;
+            }
+        };
 
         if job.retry_number != retry_number {
             return;
         }
 
-        // scope for the write lock
-        {
-            self.active_jobs.write().await.remove(&job_id);
+        if let Err(err) = self.job_store.complete(job_id, retry_number).await {
+            error!("Failed to remove job ID: {:?} from job store: {:?}", job_id, err);
         }
     }
 
     async fn txns_to_common_chain(
         self: Arc<Self>,
         mut rx: Receiver<(Job, Arc<CommonChainClient>)>,
+        relay_tx: Sender<(Job, Arc<CommonChainClient>)>,
     ) -> Result<()> {
         while let Some((job, com_chain_client)) = rx.recv().await {
             match job.job_type {
                 ComChainJobType::JobRelay => {
-                    com_chain_client.relay_job_txn(job).await;
+                    com_chain_client.relay_job_txn(job, relay_tx.clone()).await;
                 }
                 ComChainJobType::SlashGatewayJob => {
                     com_chain_client.reassign_gateway_relay_txn(job).await;
@@ -518,7 +968,11 @@ impl CommonChainClient {
         Ok(())
     }
 
-    async fn relay_job_txn(self: Arc<Self>, job: Job) {
+    /// `tx` is the same channel `job_placed_handler`/`JobRelayEventuality`
+    /// dispatch a `Job` on; `RelayFinalityEventuality` reuses it to
+    /// re-enqueue this job for relay under a fresh nonce if the submission
+    /// tracked below turns out to have been reorged out.
+    async fn relay_job_txn(self: Arc<Self>, job: Job, tx: Sender<(Job, Arc<CommonChainClient>)>) {
         info!("Creating a transaction for relayJob");
         let signature = sign_relay_job_response(
             &self.enclave_signer_key,
@@ -547,28 +1001,57 @@ impl CommonChainClient {
             job.job_owner,
         );
 
-        let pending_txn = txn.send().await;
-        let Ok(pending_txn) = pending_txn else {
+        let _rotation_guard = self.gas_wallet_rotation_barrier.read().await;
+        let provider_stack = self.gas_wallet.read().await.provider_stack.clone();
+        let confirmation_timer = self.metrics.relay_confirmation_latency.start_timer();
+        let receipt = TxnManager::send(
+            self.com_chain_jobs_contract.client().as_ref(),
+            &provider_stack,
+            txn,
+            job.max_gas_price,
+        )
+        .await;
+        confirmation_timer.observe_duration();
+        let Ok(receipt) = receipt else {
             error!(
-                "Failed to confirm transaction {} for job relay to CommonChain",
-                pending_txn.unwrap_err()
+                "Failed to relay job ID {:?} to CommonChain: {:?}",
+                job.job_id,
+                receipt.unwrap_err()
             );
             return;
         };
 
-        let txn_hash = pending_txn.tx_hash();
-        let Ok(Some(_)) = pending_txn.confirmations(1).await else {
+        info!(
+            "Transaction {:?} confirmed for job relay to CommonChain; tracking finality",
+            receipt.transaction_hash
+        );
+
+        let submitted_block = receipt.block_number.map(|n| n.as_u64()).unwrap_or_default();
+        if let Err(err) = self
+            .job_store
+            .record_relay_submission(job.job_id, receipt.transaction_hash, submitted_block)
+            .await
+        {
             error!(
-                "Failed to confirm transaction {} for job relay to CommonChain",
-                txn_hash
+                "Failed to record relay submission for job ID {:?}: {:?}",
+                job.job_id, err
             );
-            return;
-        };
+        }
 
-        info!(
-            "Transaction {} confirmed for job relay to CommonChain",
-            txn_hash
-        );
+        let deadline = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + RELAY_FINALITY_TIMEOUT.as_secs();
+        self.eventuality_tracker
+            .track(Box::new(RelayFinalityEventuality {
+                client: self.clone(),
+                job,
+                tx_hash: receipt.transaction_hash,
+                tx,
+                deadline,
+            }))
+            .await;
     }
 
     async fn reassign_gateway_relay_txn(self: Arc<Self>, job: Job) {
@@ -590,27 +1073,29 @@ impl CommonChainClient {
             job.retry_number,
         );
 
-        let pending_txn = txn.send().await;
-        let Ok(pending_txn) = pending_txn else {
-            error!(
-                "Failed to confirm transaction {} for reassign gateway relay to CommonChain",
-                pending_txn.unwrap_err()
-            );
-            return;
-        };
-
-        let txn_hash = pending_txn.tx_hash();
-        let Ok(Some(_)) = pending_txn.confirmations(1).await else {
+        let _rotation_guard = self.gas_wallet_rotation_barrier.read().await;
+        let provider_stack = self.gas_wallet.read().await.provider_stack.clone();
+        let confirmation_timer = self.metrics.reassign_confirmation_latency.start_timer();
+        let receipt = TxnManager::send(
+            self.com_chain_jobs_contract.client().as_ref(),
+            &provider_stack,
+            txn,
+            job.max_gas_price,
+        )
+        .await;
+        confirmation_timer.observe_duration();
+        let Ok(receipt) = receipt else {
             error!(
-                "Failed to confirm transaction {} for reassign gateway relay to CommonChain",
-                txn_hash
+                "Failed to reassign gateway relay for job ID {:?} on CommonChain: {:?}",
+                job.job_id,
+                receipt.unwrap_err()
             );
             return;
         };
 
         info!(
-            "Transaction {} confirmed for reassign gateway relay to CommonChain",
-            txn_hash
+            "Transaction {:?} confirmed for reassign gateway relay to CommonChain",
+            receipt.transaction_hash
         );
     }
 
@@ -627,256 +1112,454 @@ impl CommonChainClient {
                 keccak256("JobResourceUnavailable(uint256,uint256,address)"),
             ]);
 
-        let mut stream = self
+        let self_clone = Arc::clone(&self);
+        task::spawn(async move {
+            let mut stream = self_clone
+                .chain_ws_client
+                .subscribe_logs(&event_filter)
+                .await
+                .context("failed to subscribe to events on the Common Chain")
+                .unwrap();
+
+            while let Some(log) = stream.next().await {
+                let orphaned = self_clone
+                    .com_chain_confirmation_buffer
+                    .write()
+                    .await
+                    .ingest(log);
+                for orphaned_log in orphaned {
+                    warn!(
+                        "Common Chain reorg orphaned already-acted-on log: {:?}",
+                        orphaned_log
+                    );
+                }
+            }
+        });
+
+        let mut block_stream = self
             .chain_ws_client
-            .subscribe_logs(&event_filter)
+            .subscribe_blocks()
             .await
-            .context("failed to subscribe to events on the Common Chain")
+            .context("failed to subscribe to new blocks on the Common Chain")
             .unwrap();
 
-        while let Some(log) = stream.next().await {
-            let topics = log.topics.clone();
-
-            if topics[0]
-                == keccak256("JobResponded(uint256,uint256,address,bytes,uint256,uint256,uint8)")
-                    .into()
-            {
-                info!(
-                    "JobResponded event triggered for job ID: {:?}",
-                    log.topics[1]
-                );
-                let self_clone = Arc::clone(&self);
-                let tx = tx.clone();
-                task::spawn(async move {
-                    let job_response = self_clone
-                        .clone()
-                        .get_job_from_job_responded_event(log)
-                        .await
-                        .context("Failed to decode event")
-                        .unwrap();
-                    self_clone.job_responded_handler(job_response, tx).await;
-                    // TODO: remove job from active jobs list once txn to req chain is completed
-                });
-            } else if topics[0]
-                == keccak256("JobResourceUnavailable(uint256,uint256,address)").into()
-            {
-                info!("JobResourceUnavailable event triggered");
-                let self_clone = Arc::clone(&self);
-                task::spawn(async move {
-                    self_clone.job_resource_unavailable_handler(log).await;
-                });
-            } else {
-                error!("Unknown event: {:?}", log);
+        while let Some(block) = block_stream.next().await {
+            let Some(head_block_number) = block.number else {
+                continue;
+            };
+            let confirmed_logs = self
+                .com_chain_confirmation_buffer
+                .write()
+                .await
+                .confirmed_logs(head_block_number.as_u64());
+            for log in confirmed_logs {
+                Arc::clone(&self)
+                    .dispatch_com_chain_log(log, tx.clone())
+                    .await;
             }
         }
 
         Ok(())
     }
 
-    async fn get_job_from_job_responded_event(self: Arc<Self>, log: Log) -> Result<JobResponse> {
-        let types = vec![
-            ParamType::Uint(256),
-            ParamType::Uint(256),
-            ParamType::Address,
-            ParamType::Bytes,
-            ParamType::Uint(256),
-            ParamType::Uint(8),
-            ParamType::Uint(8),
-        ];
+    /// Decode one confirmed Common Chain log and route it to the matching
+    /// handler. Split out of `handle_all_com_chain_events` so the
+    /// block-header subscriber that drains the `ConfirmationBuffer` can
+    /// reuse it.
+    async fn dispatch_com_chain_log(self: Arc<Self>, log: Log, tx: Sender<(JobResponse, Arc<CommonChainClient>)>) {
+        let topics = log.topics.clone();
 
-        let decoded = decode(&types, &log.data.0).unwrap();
+        if topics[0]
+            == keccak256("JobResponded(uint256,uint256,address,bytes,uint256,uint256,uint8)")
+                .into()
+        {
+            tracing::info!(job_id = ?log.topics[1], "JobResponded event triggered");
+            task::spawn(async move {
+                let job_response = match self.clone().get_job_from_job_responded_event(log).await {
+                    Ok(job_response) => job_response,
+                    Err(err) => {
+                        self.dead_letter(err).await;
+                        return;
+                    }
+                };
+                if let Err(err) = self.clone().job_responded_handler(job_response, tx).await {
+                    self.dead_letter(err).await;
+                }
+                // TODO: remove job from active jobs list once txn to req chain is completed
+            });
+        } else if topics[0] == keccak256("JobResourceUnavailable(uint256,uint256,address)").into()
+        {
+            info!("JobResourceUnavailable event triggered");
+            task::spawn(async move {
+                if let Err(err) = self.clone().job_resource_unavailable_handler(log).await {
+                    self.dead_letter(err).await;
+                }
+            });
+        } else {
+            error!("Unknown event: {:?}", log);
+        }
+    }
+
+    async fn get_job_from_job_responded_event(
+        self: Arc<Self>,
+        log: Log,
+    ) -> Result<JobResponse, GatewayError> {
+        let types = vec![
+            ParamType::Uint(256),
+            ParamType::Uint(256),
+            ParamType::Address,
+            ParamType::Bytes,
+            ParamType::Uint(256),
+            ParamType::Uint(8),
+            ParamType::Uint(8),
+        ];
+
+        let raw = hex::encode(&log.data.0);
+        let decoded = decode(&types, &log.data.0).map_err(|source| GatewayError::InvalidEvent {
+            source,
+            raw: raw.clone(),
+        })?;
+        let invalid = || GatewayError::InvalidEvent {
+            source: AbiError::InvalidData,
+            raw: raw.clone(),
+        };
 
         Ok(JobResponse {
-            job_id: decoded[0].clone().into_uint().unwrap(),
-            req_chain_id: decoded[1].clone().into_uint().unwrap(),
-            output: decoded[2].clone().into_bytes().unwrap().into(),
-            total_time: decoded[3].clone().into_uint().unwrap(),
-            error_code: decoded[4].clone().into_uint().unwrap().low_u64() as u8,
-            output_count: decoded[5].clone().into_uint().unwrap().low_u64() as u8,
+            job_id: decoded[0].clone().into_uint().ok_or_else(invalid)?,
+            req_chain_id: decoded[1].clone().into_uint().ok_or_else(invalid)?,
+            output: decoded[2].clone().into_bytes().ok_or_else(invalid)?.into(),
+            total_time: decoded[3].clone().into_uint().ok_or_else(invalid)?,
+            error_code: decoded[4]
+                .clone()
+                .into_uint()
+                .ok_or_else(invalid)?
+                .low_u64() as u8,
+            output_count: decoded[5]
+                .clone()
+                .into_uint()
+                .ok_or_else(invalid)?
+                .low_u64() as u8,
             job_type: ReqChainJobType::JobResponded,
             gateway_address: decoded[6].clone().into_address(),
             retry_number: 0,
+            // Recomputed by `finish_job_response_assignment` once the
+            // gateway assignment for this attempt is settled.
+            next_attempt_at: 0,
         })
     }
 
+    #[tracing::instrument(
+        name = "job_response",
+        skip(self, job_response, tx),
+        fields(
+            job_id = %job_response.job_id,
+            req_chain_id = job_response.req_chain_id,
+            retry_number = job_response.retry_number,
+            gateway_address = ?job_response.gateway_address,
+            correlation_id = %job_response_correlation_id(job_response.job_id),
+        )
+    )]
     async fn job_responded_handler(
         self: Arc<Self>,
         job_response: JobResponse,
         tx: Sender<(JobResponse, Arc<CommonChainClient>)>,
-    ) {
-        let mut job_response: JobResponse = job_response.clone();
-        let req_chain_client =
-            self.req_chain_clients[&job_response.req_chain_id.to_string()].clone();
+    ) -> Result<(), GatewayError> {
+        let job_response: JobResponse = job_response.clone();
 
         // You get the selected gateway address in the event.
         if job_response.gateway_address.unwrap() == self.address {
-            let job: Job;
-            // scope for the read lock
+            let job = self
+                .job_store
+                .info(job_response.job_id)
+                .await
+                .map_err(GatewayError::JobStore)?
+                .ok_or(GatewayError::MissingActiveJob(job_response.job_id))?;
+
+            if let Err(err) = self
+                .job_store
+                .release_response_lease(job_response.job_id)
+                .await
             {
-                job = self
-                    .active_jobs
-                    .read()
-                    .await
-                    .get(&job_response.job_id)
-                    .unwrap()
-                    .clone();
+                tracing::error!(error = ?err, "failed to release response lease");
             }
             self.clone().remove_job(job).await;
-        } else if job_response.retry_number > 0 {
-            let gateway_address: Address;
-            // let seed be absolute difference between (job_id and req_chain_id) + total_time
-            let seed = {
-                let job_id_req_chain_id = match job_response
-                    .job_id
-                    .as_u64()
-                    .checked_sub(job_response.req_chain_id.as_u64())
-                {
-                    Some(val) => val,
-                    None => job_response.req_chain_id.as_u64() - job_response.job_id.as_u64(),
-                };
-                job_id_req_chain_id + job_response.total_time.as_u64()
-            };
-
-            gateway_address = self
-                .select_gateway_for_job_id(
-                    job_response.job_id.clone(),
-                    seed,
-                    job_response.retry_number,
-                    req_chain_client,
-                )
-                .await
-                .context("Failed to select a gateway for the job")
-                .unwrap();
-
-            job_response.gateway_address = Some(gateway_address);
+            return Ok(());
         }
 
-        if job_response.gateway_address.unwrap() == self.address {
-            tx.send((job_response, self.clone())).await.unwrap();
+        if job_response.retry_number > 0 {
+            self.reassign_job_response(job_response, tx).await;
         } else {
-            self.job_responded_slash_timer(job_response.clone(), tx.clone())
-                .await
-                .unwrap();
-        }
-    }
-
-    async fn remove_job(self: Arc<Self>, job: Job) {
-        let mut active_jobs = self.active_jobs.write().await;
-        // The retry number check is to make sure we are removing the correct job from the active jobs list
-        // In a case where this txn took longer than the REQUEST_RELAY_TIMEOUT, the job might have been retried
-        // and the active_jobs list might have the same job_id with a different retry number.
-        if active_jobs.contains_key(&job.job_id)
-            && active_jobs[&job.job_id].retry_number == job.retry_number
-        {
-            active_jobs.remove(&job.job_id);
+            self.finish_job_response_assignment(job_response, tx).await;
         }
+        Ok(())
     }
 
-    #[async_recursion]
-    async fn job_responded_slash_timer(
+    /// Pick a new gateway for `job_response`, whether the retry was
+    /// triggered by an on-chain `SlashGatewayResponse` event or by
+    /// `response_lease_sweeper` reclaiming a dead gateway's lease.
+    #[tracing::instrument(
+        name = "job_response",
+        skip(self, job_response, tx),
+        fields(
+            job_id = %job_response.job_id,
+            req_chain_id = job_response.req_chain_id,
+            retry_number = job_response.retry_number,
+            gateway_address = ?job_response.gateway_address,
+            correlation_id = %job_response_correlation_id(job_response.job_id),
+        )
+    )]
+    async fn reassign_job_response(
         self: Arc<Self>,
         mut job_response: JobResponse,
         tx: Sender<(JobResponse, Arc<CommonChainClient>)>,
-    ) -> Result<()> {
-        time::sleep(Duration::from_secs(RESPONSE_RELAY_TIMEOUT)).await;
-
-        // get request chain client
+    ) {
         let req_chain_client =
             self.req_chain_clients[&job_response.req_chain_id.to_string()].clone();
 
-        let onchain_job_response = req_chain_client
-            .contract
-            .jobs(job_response.job_id)
+        // let seed be absolute difference between (job_id and req_chain_id) + total_time
+        let seed = {
+            let job_id_req_chain_id = match job_response
+                .job_id
+                .as_u64()
+                .checked_sub(job_response.req_chain_id.as_u64())
+            {
+                Some(val) => val,
+                None => job_response.req_chain_id.as_u64() - job_response.job_id.as_u64(),
+            };
+            job_id_req_chain_id + job_response.total_time.as_u64()
+        };
+
+        let gateway_address = self
+            .select_gateway_for_job_id(
+                job_response.job_id.clone(),
+                seed,
+                job_response.retry_number,
+                req_chain_client,
+            )
             .await
+            .context("Failed to select a gateway for the job")
             .unwrap();
 
-        let output_received: bool = onchain_job_response.8;
-        let onchain_job_response: JobResponse = JobResponse {
-            job_id: job_response.job_id,
-            req_chain_id: job_response.req_chain_id,
-            output: Bytes::default().into(),
-            total_time: U256::zero(),
-            error_code: 0,
-            output_count: 0,
-            job_type: ReqChainJobType::JobResponded,
-            gateway_address: Some(onchain_job_response.7),
-            // depending on how the gateway is reassigned, the retry number might be different
-            // can be added to event and a check below in the if condition
-            // if retry number is added to the event,
-            // remove_job_response needs to be updated accordingly
-            retry_number: 0,
-        };
+        job_response.gateway_address = Some(gateway_address);
 
-        if output_received && onchain_job_response.gateway_address.unwrap() != H160::zero() {
-            info!(
-                "Job ID: {:?}, JobResponded event triggered",
-                job_response.job_id
-            );
-            return Ok(());
-        }
+        self.finish_job_response_assignment(job_response, tx).await;
+    }
 
-        // TODO: how to slash the gateway now?
-        // The same function used with the JobRelayed event won't work here.
-        // For now, use the same function.
-        {
-            let self_clone = self.clone();
-            let mut job_response_clone = job_response.clone();
-            job_response_clone.job_type = ReqChainJobType::SlashGatewayResponse;
-            let tx_clone = tx.clone();
-            tx_clone
-                .send((job_response_clone, self_clone))
+    /// Act on `job_response`'s now-settled `gateway_address`: take the
+    /// response lease and submit it ourselves, or resume watching another
+    /// gateway via `JobResponseEventuality`.
+    #[tracing::instrument(
+        name = "job_response",
+        skip(self, job_response, tx),
+        fields(
+            job_id = %job_response.job_id,
+            req_chain_id = job_response.req_chain_id,
+            retry_number = job_response.retry_number,
+            gateway_address = ?job_response.gateway_address,
+            correlation_id = %job_response_correlation_id(job_response.job_id),
+        )
+    )]
+    async fn finish_job_response_assignment(
+        self: Arc<Self>,
+        mut job_response: JobResponse,
+        tx: Sender<(JobResponse, Arc<CommonChainClient>)>,
+    ) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let policy = self.retry_policy.policy_for(&job_response.job_type);
+        job_response.next_attempt_at = policy.next_attempt_at(now, job_response.retry_number);
+
+        if job_response.gateway_address.unwrap() == self.address {
+            if let Err(err) = self
+                .job_store
+                .acquire_response_lease(&job_response, &self.runner_id, now)
                 .await
-                .unwrap();
+            {
+                tracing::error!(error = ?err, "failed to acquire response lease");
+            }
+            self.clone()
+                .spawn_response_lease_heartbeat(job_response.job_id);
+            tx.send((job_response, self.clone())).await.unwrap();
+        } else {
+            let response_deadline = job_response.next_attempt_at;
+            self.eventuality_tracker
+                .track(Box::new(JobResponseEventuality {
+                    client: self.clone(),
+                    job_response,
+                    tx,
+                    deadline: response_deadline,
+                }))
+                .await;
         }
+    }
 
-        job_response.retry_number += 1;
-        if job_response.retry_number >= MAX_GATEWAY_RETRIES {
-            info!("Job ID: {:?}, Max retries reached", job_response.job_id);
-            return Ok(());
+    /// Refresh this gateway's response lease heartbeat for `job_id` at
+    /// `LEASE_HEARTBEAT_INTERVAL`, stopping once the lease is released or
+    /// reclaimed out from under it (`heartbeat_response_lease` returns
+    /// `false`), instead of heartbeating a lease that's no longer ours.
+    fn spawn_response_lease_heartbeat(self: Arc<Self>, job_id: U256) {
+        let span = tracing::info_span!(
+            "response_lease_heartbeat",
+            job_id = %job_id,
+            correlation_id = %job_response_correlation_id(job_id),
+        );
+        task::spawn(
+            async move {
+                let mut interval = time::interval(LEASE_HEARTBEAT_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    let now = SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs();
+                    match self
+                        .job_store
+                        .heartbeat_response_lease(job_id, &self.runner_id, now)
+                        .await
+                    {
+                        Ok(true) => {}
+                        Ok(false) => return,
+                        Err(err) => {
+                            tracing::error!(error = ?err, "failed to heartbeat response lease")
+                        }
+                    }
+                }
+            }
+            .instrument(span),
+        );
+    }
+
+    /// Scans response leases for ones whose heartbeat has gone stale,
+    /// meaning the gateway that took ownership of submitting that job's
+    /// response has likely died, and reassigns them. Runs at
+    /// `LEASE_HEARTBEAT_INTERVAL` so a dead gateway's jobs get picked up in
+    /// `LEASE_TTL`, well before the much longer `RetryPolicy`-governed
+    /// deadline that `JobResponseEventuality` otherwise waits out.
+    async fn response_lease_sweeper(
+        self: Arc<Self>,
+        tx: Sender<(JobResponse, Arc<CommonChainClient>)>,
+    ) {
+        let mut interval = time::interval(LEASE_HEARTBEAT_INTERVAL);
+        loop {
+            interval.tick().await;
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+
+            let reclaimed = match self
+                .job_store
+                .reclaim_expired_response_leases(LEASE_TTL.as_secs(), now)
+                .await
+            {
+                Ok(reclaimed) => reclaimed,
+                Err(err) => {
+                    error!("Failed to scan response leases: {:?}", err);
+                    continue;
+                }
+            };
+
+            for mut job_response in reclaimed {
+                tracing::warn!(
+                    job_id = %job_response.job_id,
+                    correlation_id = %job_response_correlation_id(job_response.job_id),
+                    "response lease expired; reassigning",
+                );
+                job_response.retry_number += 1;
+                self.clone()
+                    .reassign_job_response(job_response, tx.clone())
+                    .await;
+            }
         }
+    }
 
-        // If gateway is already set, job_responded_handler will reassign the gateway
-        job_response.gateway_address = onchain_job_response.gateway_address;
-        self.job_responded_handler(job_response, tx).await;
+    async fn remove_job(self: Arc<Self>, job: Job) {
+        if let Err(err) = self.job_store.complete(job.job_id, job.retry_number).await {
+            tracing::error!(
+                job_id = %job.job_id,
+                error = ?err,
+                "failed to remove job from job store",
+            );
+        }
+    }
 
-        Ok(())
+    /// Log `err` with its structured error code and persist it to the
+    /// `DeadLetterStore` instead of letting the caller unwrap or silently
+    /// drop it, so a malformed or out-of-order event can be inspected or
+    /// replayed later rather than taking down the event task.
+    async fn dead_letter(&self, err: GatewayError) {
+        tracing::error!(error_code = err.code(), error = %err, "event sent to dead-letter store");
+
+        let raw = match &err {
+            GatewayError::InvalidEvent { raw, .. } => raw.clone(),
+            GatewayError::MissingActiveJob(_)
+            | GatewayError::MissingGatewayAddress(_)
+            | GatewayError::JobStore(_)
+            | GatewayError::Provider(_) => {
+                String::new()
+            }
+        };
+        let entry = DeadLetterEntry {
+            error_code: err.code().to_string(),
+            message: err.to_string(),
+            raw,
+            occurred_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+        };
+        if let Err(store_err) = self.dead_letter_store.record(entry).await {
+            tracing::error!(error = ?store_err, "failed to persist dead-letter entry");
+        }
     }
 
-    async fn job_resource_unavailable_handler(self: Arc<Self>, log: Log) {
+    async fn job_resource_unavailable_handler(self: Arc<Self>, log: Log) -> Result<(), GatewayError> {
         let types = vec![
             ParamType::Uint(256),
             ParamType::Uint(256),
             ParamType::Address,
         ];
 
-        let decoded = decode(&types, &log.data.0).unwrap();
+        let raw = hex::encode(&log.data.0);
+        let decoded = decode(&types, &log.data.0).map_err(|source| GatewayError::InvalidEvent {
+            source,
+            raw: raw.clone(),
+        })?;
+        let invalid = || GatewayError::InvalidEvent {
+            source: AbiError::InvalidData,
+            raw: raw.clone(),
+        };
 
-        let job_id = decoded[0].clone().into_uint().unwrap();
-        let req_chain_id = decoded[1].clone().into_uint().unwrap();
-        let gateway_address = decoded[2].clone().into_address().unwrap();
+        let job_id = decoded[0].clone().into_uint().ok_or_else(invalid)?;
+        let req_chain_id = decoded[1].clone().into_uint().ok_or_else(invalid)?;
+        let gateway_address = decoded[2].clone().into_address().ok_or_else(invalid)?;
 
         if gateway_address != self.address {
-            return;
+            return Ok(());
         }
 
-        let job: Job;
-        // scope for the read lock
-        {
-            job = self.active_jobs.read().await.get(&job_id).unwrap().clone();
-        }
+        let job = self
+            .job_store
+            .info(job_id)
+            .await
+            .map_err(GatewayError::JobStore)?
+            .ok_or(GatewayError::MissingActiveJob(job_id))?;
 
         if job.req_chain_id != req_chain_id.as_u64() {
-            return;
+            return Ok(());
         }
 
         if job.gateway_address.unwrap() != gateway_address {
-            return;
+            return Ok(());
         }
 
-        // scope for the write lock
-        {
-            self.active_jobs.write().await.remove(&job_id);
+        if let Err(err) = self.job_store.complete(job_id, job.retry_number).await {
+            tracing::error!(%job_id, error = ?err, "failed to remove job from job store");
         }
+        Ok(())
     }
 
     async fn txns_to_request_chain(
@@ -888,9 +1571,12 @@ impl CommonChainClient {
                 ReqChainJobType::JobResponded => {
                     let com_chain_client_clone = com_chain_client.clone();
                     let job_response_clone = job_response.clone();
-                    com_chain_client_clone
+                    if let Err(err) = com_chain_client_clone
                         .job_response_txn(job_response_clone)
-                        .await;
+                        .await
+                    {
+                        com_chain_client.dead_letter(err).await;
+                    }
                     com_chain_client
                         .remove_job_response(job_response.job_id)
                         .await;
@@ -906,8 +1592,25 @@ impl CommonChainClient {
         Ok(())
     }
 
-    async fn job_response_txn(self: Arc<Self>, job_response: JobResponse) {
-        info!("Creating a transaction for jobResponse");
+    #[tracing::instrument(
+        name = "job_response",
+        skip(self, job_response),
+        fields(
+            job_id = %job_response.job_id,
+            req_chain_id = job_response.req_chain_id,
+            retry_number = job_response.retry_number,
+            gateway_address = ?job_response.gateway_address,
+            correlation_id = %job_response_correlation_id(job_response.job_id),
+        )
+    )]
+    async fn job_response_txn(self: Arc<Self>, job_response: JobResponse) -> Result<(), GatewayError> {
+        tracing::info!("creating a transaction for jobResponse");
+
+        // Persist that this response is being submitted before awaiting
+        // confirmations. Restart rehydration does not read this status.
+        if let Err(err) = self.job_store.mark_running(job_response.job_id).await {
+            tracing::error!(error = ?err, "failed to persist in-flight response state");
+        }
 
         let req_chain_client =
             self.req_chain_clients[&job_response.req_chain_id.to_string()].clone();
@@ -923,40 +1626,511 @@ impl CommonChainClient {
         .unwrap();
         let signature = types::Bytes::from(signature.into_bytes());
 
-        let txn = req_chain_client.contract.job_response(
+        let mut txn = req_chain_client.contract.job_response(
             signature,
             job_response.job_id,
             job_response.output,
             job_response.total_time,
             job_response.error_code,
         );
+        req_chain_client
+            .provider_stack
+            .prepare(req_chain_client.contract.client().as_ref(), &mut txn.tx)
+            .await
+            .map_err(GatewayError::Provider)?;
 
-        let pending_txn = txn.send().await;
-        let Ok(pending_txn) = pending_txn else {
-            error!(
-                "Failed to confirm transaction {} for job response to RequestChain",
-                pending_txn.unwrap_err()
-            );
-            return;
+        let pending_txn = loop {
+            let send_result = with_timeout(
+                "job_response_txn.send",
+                RPC_TIMEOUT,
+                txn.send().with_poll_timer("job_response_txn.send"),
+            )
+            .await;
+            match send_result {
+                Ok(Ok(pending_txn)) => break pending_txn,
+                Ok(Err(err)) if is_nonce_error(&err.to_string()) => {
+                    tracing::warn!(error = %err, "job response txn rejected on nonce; resyncing nonce manager");
+                    if let Err(resync_err) = req_chain_client
+                        .provider_stack
+                        .nonce_manager
+                        .resync(req_chain_client.contract.client().as_ref())
+                        .await
+                    {
+                        tracing::error!(error = ?resync_err, "failed to resync nonce manager");
+                        return Err(GatewayError::Provider(resync_err));
+                    }
+                    txn.tx.set_nonce(
+                        req_chain_client.provider_stack.nonce_manager.next_nonce(),
+                    );
+                }
+                Ok(Err(err)) => {
+                    tracing::error!(error = ?err, "failed to submit job response txn to RequestChain");
+                    return Err(GatewayError::Provider(err.into()));
+                }
+                Err(err) => {
+                    tracing::error!(error = ?err, "job response txn send timed out");
+                    return Err(GatewayError::Provider(err.into()));
+                }
+            }
         };
 
         let txn_hash = pending_txn.tx_hash();
-        let Ok(Some(_)) = pending_txn.confirmations(1).await else {
+        let confirmations = with_timeout(
+            "job_response_txn.confirmations",
+            RPC_TIMEOUT,
+            pending_txn
+                .confirmations(1)
+                .with_poll_timer("job_response_txn.confirmations"),
+        )
+        .await;
+        match confirmations {
+            Ok(Ok(Some(_))) => {}
+            Ok(Ok(None)) | Ok(Err(_)) => {
+                tracing::error!(%txn_hash, "failed to confirm job response txn to RequestChain");
+                return Err(GatewayError::Provider(anyhow::anyhow!(
+                    "transaction {} failed to confirm on RequestChain",
+                    txn_hash
+                )));
+            }
+            Err(err) => {
+                tracing::error!(%txn_hash, error = ?err, "job response txn confirmation timed out");
+                return Err(GatewayError::Provider(err.into()));
+            }
+        }
+
+        tracing::info!(%txn_hash, "job response txn confirmed on RequestChain");
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "job_response",
+        skip(self),
+        fields(job_id = %job_id, correlation_id = %job_response_correlation_id(job_id))
+    )]
+    async fn remove_job_response(self: Arc<Self>, job_id: U256) {
+        let retry_number = match self.job_store.info(job_id).await {
+            Ok(Some(job)) => job.retry_number,
+            Ok(None) => return,
+            Err(err) => {
+                tracing::error!(error = ?err, "failed to look up job in job store");
+                return;
+            }
+        };
+
+        if let Err(err) = self.job_store.complete(job_id, retry_number).await {
+            tracing::error!(error = ?err, "failed to remove job from job store");
+        }
+    }
+}
+
+/// Watches for a placed job's `relayJob` transaction to land on the Common
+/// Chain before `deadline`. Replaces the old `sleep`-then-check
+/// `job_relayed_slash_timer`: `confirm_completion` is polled by the shared
+/// `EventualityTracker` instead of a dedicated task per job.
+struct JobRelayEventuality {
+    client: Arc<CommonChainClient>,
+    job: Job,
+    tx: Sender<(Job, Arc<CommonChainClient>)>,
+    deadline: u64,
+}
+
+#[async_trait::async_trait]
+impl Eventuality for JobRelayEventuality {
+    async fn confirm_completion(&self) -> Result<Completion> {
+        let job_key = U256::from(keccak256(format!(
+            "{}-{}",
+            self.job.job_id, self.job.req_chain_id
+        )));
+        let onchain_job = with_timeout(
+            "job_relay_eventuality.jobs",
+            RPC_TIMEOUT,
+            self.client
+                .com_chain_jobs_contract
+                .jobs(job_key)
+                .with_poll_timer("job_relay_eventuality.jobs"),
+        )
+        .await
+        .map_err(anyhow::Error::from)??;
+
+        if onchain_job.0 != self.job.job_id {
+            // Not yet visible at this key on the Common Chain; keep waiting.
+            return Ok(Completion::Pending);
+        }
+
+        if onchain_job.9 != self.job.retry_number {
+            // Another path already advanced this job past the retry number
+            // this eventuality is watching for.
+            return Ok(Completion::Superseded);
+        }
+
+        let relayed = onchain_job.2 != FixedBytes::default()
+            && onchain_job.3 != Bytes::default()
+            && onchain_job.4 != U256::zero()
+            && onchain_job.5 != U256::zero()
+            && onchain_job.1.as_u64() != 0
+            && onchain_job.6 != H160::zero()
+            && onchain_job.7 != H160::zero();
+
+        if relayed {
+            info!("Job ID: {:?}, JobRelayed event triggered", self.job.job_id);
+            if let Err(err) = self
+                .client
+                .job_store
+                .complete(self.job.job_id, self.job.retry_number)
+                .await
+            {
+                error!(
+                    "Failed to remove job ID: {:?} from job store: {:?}",
+                    self.job.job_id, err
+                );
+            }
+            Ok(Completion::Completed)
+        } else {
+            Ok(Completion::Pending)
+        }
+    }
+
+    fn deadline(&self) -> u64 {
+        self.deadline
+    }
+
+    async fn on_timeout(self: Box<Self>) {
+        let JobRelayEventuality {
+            client,
+            mut job,
+            tx,
+            ..
+        } = *self;
+
+        client
+            .metrics
+            .slash_timer_firings
+            .with_label_values(&["relay"])
+            .inc();
+
+        // slash the previous gateway
+        {
+            let mut job_clone = job.clone();
+            job_clone.job_type = ComChainJobType::SlashGatewayJob;
+            tx.send((job_clone, client.clone())).await.unwrap();
+        }
+
+        if let Some(gateway_address) = job.gateway_address {
+            client
+                .gateway_selector
+                .record_relay_failure(gateway_address)
+                .await;
+        }
+
+        let stale_retry_number = job.retry_number;
+        job.retry_number += 1;
+        if job.retry_number >= MAX_GATEWAY_RETRIES {
+            info!("Job ID: {:?}, Max retries reached", job.job_id);
+            if let Err(err) = client.job_store.complete(job.job_id, stale_retry_number).await {
+                error!(
+                    "Failed to remove job ID: {:?} from job store: {:?}",
+                    job.job_id, err
+                );
+            }
+            return;
+        }
+        job.gateway_address = None;
+
+        client
+            .job_placed_handler(&job.req_chain_id.to_string(), job, tx)
+            .await;
+    }
+}
+
+/// Watches a `relayJob` transaction this gateway itself submitted for
+/// finality on the Common Chain, rather than merely its first confirmation:
+/// an L2 reorg can drop a transaction that already had one confirmation, so
+/// `confirm_completion` keeps re-checking the receipt until it's buried
+/// under `RELAY_FINALITY_CONFIRMATION_DEPTH` confirmations and its
+/// `JobRelayed` log is still present at that point — the same "check the
+/// event also exists alongside the transfer" invariant the Serai Ethereum
+/// processor uses for InInstructions. If the receipt disappears, reverts,
+/// or loses its log before then, the job is re-relayed under a fresh nonce
+/// instead of being left stranded.
+struct RelayFinalityEventuality {
+    client: Arc<CommonChainClient>,
+    job: Job,
+    tx_hash: H256,
+    tx: Sender<(Job, Arc<CommonChainClient>)>,
+    deadline: u64,
+}
+
+impl RelayFinalityEventuality {
+    /// Drop the stale submission record and re-enqueue the job for relay
+    /// under whatever nonce is current at send time, rather than the one
+    /// that was reorged out.
+    async fn rebroadcast(&self) -> Result<()> {
+        self.client.metrics.relay_finality_rebroadcasts.inc();
+
+        if let Err(err) = self
+            .client
+            .job_store
+            .clear_relay_submission(self.job.job_id)
+            .await
+        {
             error!(
-                "Failed to confirm transaction {} for job response to RequestChain",
-                txn_hash
+                "Failed to clear relay submission for job ID {:?}: {:?}",
+                self.job.job_id, err
             );
-            return;
+        }
+
+        let mut job = self.job.clone();
+        job.job_type = ComChainJobType::JobRelay;
+        self.tx
+            .send((job, self.client.clone()))
+            .await
+            .context("failed to re-enqueue job for relay")
+    }
+}
+
+#[async_trait::async_trait]
+impl Eventuality for RelayFinalityEventuality {
+    async fn confirm_completion(&self) -> Result<Completion> {
+        let provider = self.client.com_chain_jobs_contract.client();
+
+        let receipt = with_timeout(
+            "relay_finality_eventuality.get_transaction_receipt",
+            RPC_TIMEOUT,
+            provider
+                .get_transaction_receipt(self.tx_hash)
+                .with_poll_timer("relay_finality_eventuality.get_transaction_receipt"),
+        )
+        .await
+        .map_err(anyhow::Error::from)??;
+
+        let Some(receipt) = receipt else {
+            warn!(
+                "Job ID: {:?}, relay transaction {:?} no longer found; reorged out before finality, re-relaying",
+                self.job.job_id, self.tx_hash
+            );
+            self.rebroadcast().await?;
+            return Ok(Completion::Superseded);
+        };
+
+        if receipt.status != Some(U64::from(1)) {
+            warn!(
+                "Job ID: {:?}, relay transaction {:?} reverted; re-relaying",
+                self.job.job_id, self.tx_hash
+            );
+            self.rebroadcast().await?;
+            return Ok(Completion::Superseded);
+        }
+
+        let Some(block_number) = receipt.block_number else {
+            return Ok(Completion::Pending);
         };
 
+        let current_block = with_timeout(
+            "relay_finality_eventuality.get_block_number",
+            RPC_TIMEOUT,
+            provider
+                .get_block_number()
+                .with_poll_timer("relay_finality_eventuality.get_block_number"),
+        )
+        .await
+        .map_err(anyhow::Error::from)??;
+
+        if current_block.as_u64().saturating_sub(block_number.as_u64())
+            < RELAY_FINALITY_CONFIRMATION_DEPTH
+        {
+            return Ok(Completion::Pending);
+        }
+
+        let relayed_event_present = receipt.logs.iter().any(|log| {
+            log.address == self.client.com_chain_jobs_contract.address()
+                && log.topics.first() == Some(&H256::from(keccak256(COMMON_CHAIN_JOB_RELAYED_EVENT)))
+        });
+
+        if !relayed_event_present {
+            warn!(
+                "Job ID: {:?}, relay transaction {:?} confirmed without its JobRelayed log; re-relaying",
+                self.job.job_id, self.tx_hash
+            );
+            self.rebroadcast().await?;
+            return Ok(Completion::Superseded);
+        }
+
         info!(
-            "Transaction {} confirmed for job response to RequestChain",
-            txn_hash
+            "Job ID: {:?}, relay transaction {:?} finalized after {} confirmations",
+            self.job.job_id, self.tx_hash, RELAY_FINALITY_CONFIRMATION_DEPTH
         );
+        if let Err(err) = self
+            .client
+            .job_store
+            .clear_relay_submission(self.job.job_id)
+            .await
+        {
+            error!(
+                "Failed to clear relay submission for job ID {:?}: {:?}",
+                self.job.job_id, err
+            );
+        }
+        Ok(Completion::Completed)
     }
 
-    async fn remove_job_response(self: Arc<Self>, job_id: U256) {
-        let mut active_jobs = self.active_jobs.write().await;
-        active_jobs.remove(&job_id);
+    fn deadline(&self) -> u64 {
+        self.deadline
+    }
+
+    async fn on_timeout(self: Box<Self>) {
+        warn!(
+            "Job ID: {:?}, relay transaction {:?} did not reach finality within the timeout; re-relaying",
+            self.job.job_id, self.tx_hash
+        );
+        if let Err(err) = self.rebroadcast().await {
+            error!(
+                "Failed to re-relay job ID {:?} after finality timeout: {:?}",
+                self.job.job_id, err
+            );
+        }
+    }
+}
+
+/// Watches for a `JobResponded` event with output to land on the Request
+/// Chain before `deadline`. Replaces the old `sleep`-then-check
+/// `job_responded_slash_timer`, sharing the same `EventualityTracker`
+/// poller as the relay side instead of its own task.
+struct JobResponseEventuality {
+    client: Arc<CommonChainClient>,
+    job_response: JobResponse,
+    tx: Sender<(JobResponse, Arc<CommonChainClient>)>,
+    deadline: u64,
+}
+
+#[async_trait::async_trait]
+impl Eventuality for JobResponseEventuality {
+    async fn confirm_completion(&self) -> Result<Completion> {
+        let req_chain_client = self
+            .client
+            .req_chain_clients
+            .get(&self.job_response.req_chain_id.to_string())
+            .context("Request chain client not found for job response")?
+            .clone();
+
+        let onchain_job_response = with_timeout(
+            "job_response_eventuality.jobs",
+            RPC_TIMEOUT,
+            req_chain_client
+                .contract
+                .jobs(self.job_response.job_id)
+                .with_poll_timer("job_response_eventuality.jobs"),
+        )
+        .await
+        .map_err(anyhow::Error::from)??;
+
+        let output_received: bool = onchain_job_response.8;
+        let onchain_gateway_address = onchain_job_response.7;
+
+        if output_received && onchain_gateway_address != H160::zero() {
+            info!(
+                "Job ID: {:?}, JobResponded event triggered",
+                self.job_response.job_id
+            );
+            Ok(Completion::Completed)
+        } else {
+            Ok(Completion::Pending)
+        }
+    }
+
+    fn deadline(&self) -> u64 {
+        self.deadline
+    }
+
+    async fn on_timeout(self: Box<Self>) {
+        let JobResponseEventuality {
+            client,
+            mut job_response,
+            tx,
+            ..
+        } = *self;
+
+        client
+            .metrics
+            .slash_timer_firings
+            .with_label_values(&["response"])
+            .inc();
+
+        let req_chain_client = client
+            .req_chain_clients
+            .get(&job_response.req_chain_id.to_string())
+            .cloned();
+        let onchain_gateway_address = match req_chain_client {
+            Some(req_chain_client) => match with_timeout(
+                "job_response_eventuality.on_timeout.jobs",
+                RPC_TIMEOUT,
+                req_chain_client
+                    .contract
+                    .jobs(job_response.job_id)
+                    .with_poll_timer("job_response_eventuality.on_timeout.jobs"),
+            )
+            .await
+            {
+                Ok(Ok(onchain_job_response)) => Some(onchain_job_response.7),
+                Ok(Err(err)) => {
+                    tracing::error!(
+                        job_id = %job_response.job_id,
+                        error = ?err,
+                        "failed to fetch on-chain job response",
+                    );
+                    None
+                }
+                Err(err) => {
+                    tracing::error!(
+                        job_id = %job_response.job_id,
+                        error = ?err,
+                        "on-chain job response lookup timed out",
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+
+        // TODO: how to slash the gateway now?
+        // The same function used with the JobRelayed event won't work here.
+        // For now, use the same function.
+        {
+            let mut job_response_clone = job_response.clone();
+            job_response_clone.job_type = ReqChainJobType::SlashGatewayResponse;
+            tx.send((job_response_clone, client.clone())).await.unwrap();
+        }
+
+        job_response.retry_number += 1;
+        if client
+            .retry_policy
+            .policy_for(&job_response.job_type)
+            .exhausted(job_response.retry_number)
+        {
+            info!("Job ID: {:?}, Max retries reached", job_response.job_id);
+            return;
+        }
+
+        // If gateway is already set, job_responded_handler will reassign the gateway.
+        // Only overwrite it with the freshly re-fetched value: job_responded_handler
+        // unconditionally unwraps gateway_address, so if the re-fetch above errored
+        // or timed out, clobbering it with None here would turn a transient RPC
+        // failure into a panic that kills this event task instead of just retrying
+        // with the gateway address we already knew.
+        if let Some(onchain_gateway_address) = onchain_gateway_address {
+            job_response.gateway_address = Some(onchain_gateway_address);
+        }
+        if job_response.gateway_address.is_none() {
+            tracing::error!(
+                job_id = %job_response.job_id,
+                "no gateway address available for job response after on-chain re-fetch failed; dead-lettering instead of risking a panic",
+            );
+            client
+                .dead_letter(GatewayError::MissingGatewayAddress(job_response.job_id))
+                .await;
+            return;
+        }
+        if let Err(err) = client.clone().job_responded_handler(job_response, tx).await {
+            client.dead_letter(err).await;
+        }
     }
 }
@@ -12,8 +12,77 @@ pub struct Config {
     pub com_chain_id: u64,
     pub com_chain_ws_url: String,
     pub com_chain_http_url: String,
-    pub com_chain_contract_addr: H160,
     pub start_block: u64,
+    /// Gateway-registry contracts to index. Each one gets its own
+    /// independent indexing task and its own keyed subtree of epoch state,
+    /// so a single deployment can track several registries (e.g. staging +
+    /// production, or multiple request chains) without a recompile.
+    pub gateway_contract_addrs: Vec<H160>,
+    /// Length, in seconds, of a gateway epoch cycle.
+    pub time_interval: u64,
+    /// Unix timestamp marking cycle 0.
+    pub epoch: u64,
+    /// Number of recent cycles to keep in memory / the state store before
+    /// they're pruned.
+    #[serde(default = "default_gateway_block_states_to_maintain")]
+    pub gateway_block_states_to_maintain: u64,
+    /// Backend for the gateway epoch state store: "lmdb", "sqlite" or "memory".
+    #[serde(default = "default_state_store")]
+    pub state_store: String,
+    /// Directory (LMDB) or file (SQLite) the state store is persisted to.
+    /// Unused when `state_store` is "memory".
+    #[serde(default = "default_state_store_path")]
+    pub state_store_path: String,
+    /// Width, in blocks, of each `eth_getLogs` window when scanning a
+    /// cycle's block range.
+    #[serde(default = "default_max_block_range")]
+    pub max_block_range: u64,
+    /// Cycles whose `to_block_number` is within this many blocks of the
+    /// chain head are re-checked against canonical block hashes on every
+    /// new cycle; older cycles are assumed final.
+    #[serde(default = "default_reorg_depth")]
+    pub reorg_depth: u64,
+}
+
+impl Config {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.gateway_contract_addrs.is_empty() {
+            return Err(ConfigError::Message(
+                "gateway_contract_addrs must list at least one contract address".to_string(),
+            ));
+        }
+        if self.time_interval == 0 {
+            return Err(ConfigError::Message(
+                "time_interval must be greater than zero".to_string(),
+            ));
+        }
+        if self.gateway_block_states_to_maintain == 0 {
+            return Err(ConfigError::Message(
+                "gateway_block_states_to_maintain must be greater than zero".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_gateway_block_states_to_maintain() -> u64 {
+    5
+}
+
+fn default_state_store() -> String {
+    "memory".to_string()
+}
+
+fn default_state_store_path() -> String {
+    "gateway_epoch_state".to_string()
+}
+
+fn default_max_block_range() -> u64 {
+    2000
+}
+
+fn default_reorg_depth() -> u64 {
+    12
 }
 
 impl ConfigManager {
@@ -25,6 +94,8 @@ impl ConfigManager {
         let settings = config::Config::builder()
             .add_source(File::with_name(self.path.as_str()))
             .build()?;
-        settings.try_deserialize()
+        let config: Config = settings.try_deserialize()?;
+        config.validate()?;
+        Ok(config)
     }
 }
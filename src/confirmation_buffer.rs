@@ -0,0 +1,85 @@
+use ethers::types::{Log, H256};
+use log::warn;
+use std::collections::BTreeMap;
+
+/// Holds incoming logs back until they're buried under enough confirmations
+/// to be safe from a short reorg, and keeps a small recent-block header cache
+/// (block number -> hash) so a contradicting header can be noticed before a
+/// reorged log is ever dispatched. Combines the confirmation-depth wait with
+/// a candidate/best-block header chain, so `handle_all_req_chain_events` /
+/// `handle_all_com_chain_events` no longer act on `subscribe_logs` output the
+/// instant it streams in.
+pub struct ConfirmationBuffer {
+    confirmation_depth: u64,
+    headers: BTreeMap<u64, H256>,
+    pending: BTreeMap<u64, Vec<Log>>,
+    dispatched: BTreeMap<u64, Vec<Log>>,
+}
+
+impl ConfirmationBuffer {
+    pub fn new(confirmation_depth: u64) -> Self {
+        ConfirmationBuffer {
+            confirmation_depth,
+            headers: BTreeMap::new(),
+            pending: BTreeMap::new(),
+            dispatched: BTreeMap::new(),
+        }
+    }
+
+    /// Record a freshly streamed log, keyed by the block it landed in. If
+    /// this block's hash contradicts what's cached for that height, the
+    /// chain reorged: every pending log from that height onward is dropped,
+    /// and any already-dispatched log from that height onward is returned so
+    /// the caller can emit a compensating action for it.
+    pub fn ingest(&mut self, log: Log) -> Vec<Log> {
+        let block_number = log.block_number.map(|n| n.as_u64()).unwrap_or_default();
+        let block_hash = log.block_hash.unwrap_or_default();
+
+        let mut orphaned = Vec::new();
+        if let Some(&cached_hash) = self.headers.get(&block_number) {
+            if cached_hash != block_hash {
+                warn!(
+                    "Reorg detected at block {}: cached hash {:?}, new hash {:?}. Dropping superseded pending logs.",
+                    block_number, cached_hash, block_hash
+                );
+                self.pending.retain(|height, _| *height < block_number);
+                let stale_heights: Vec<u64> =
+                    self.dispatched.range(block_number..).map(|(h, _)| *h).collect();
+                for height in stale_heights {
+                    if let Some(logs) = self.dispatched.remove(&height) {
+                        orphaned.extend(logs);
+                    }
+                }
+            }
+        }
+
+        self.headers.insert(block_number, block_hash);
+        self.pending.entry(block_number).or_default().push(log);
+        orphaned
+    }
+
+    /// Move every pending log buried under at least `confirmation_depth`
+    /// confirmations of `head_block_number` into the dispatched set, and
+    /// return them for the caller to act on.
+    pub fn confirmed_logs(&mut self, head_block_number: u64) -> Vec<Log> {
+        let confirmed_up_to = head_block_number.saturating_sub(self.confirmation_depth);
+        let still_pending = self.pending.split_off(&(confirmed_up_to + 1));
+        let newly_confirmed = std::mem::replace(&mut self.pending, still_pending);
+
+        let mut confirmed_logs = Vec::new();
+        for (height, logs) in newly_confirmed {
+            self.dispatched.entry(height).or_default().extend(logs.clone());
+            confirmed_logs.extend(logs);
+        }
+
+        // Dispatched history and the header cache only need to reach as far
+        // back as a reorg could plausibly still contradict; past that,
+        // headers would otherwise accumulate one entry per distinct block
+        // height for the life of the process.
+        let prune_before = confirmed_up_to.saturating_sub(self.confirmation_depth);
+        self.dispatched.retain(|height, _| *height >= prune_before);
+        self.headers.retain(|height, _| *height >= prune_before);
+
+        confirmed_logs
+    }
+}
@@ -1,16 +1,74 @@
 use ethers::types::U256;
 use lazy_static::lazy_static;
+use std::time::Duration;
 
 pub const REQUEST_RELAY_TIMEOUT: u64 = 40;
 
-// pub const RESPONSE_RELAY_TIMEOUT: u64 = 40;
 pub const MAX_GATEWAY_RETRIES: u8 = 2;
 pub const MAX_TX_RECEIPT_RETRIES: u8 = 5;
 pub const MAX_RETRY_ON_PROVIDER_ERROR: u8 = 5;
 
-pub const GATEWAY_BLOCK_STATES_TO_MAINTAIN: u64 = 5;
 pub const WAIT_BEFORE_CHECKING_BLOCK: u64 = 5;
 
+/// Confirmations a Common Chain log must accumulate in the
+/// `ConfirmationBuffer` before it's dispatched to a handler. Request chains
+/// set their own depth per `RequestChainData` since finality time varies by
+/// chain; the Common Chain doesn't vary per deployment, so it gets one
+/// constant.
+pub const COMMON_CHAIN_CONFIRMATION_DEPTH: u64 = 12;
+
+/// Bind address for the `GatewayClientMetrics` `/metrics` endpoint, spawned
+/// from `CommonChainClient::run`.
+pub const GATEWAY_CLIENT_METRICS_ADDR: &str = "0.0.0.0:9001";
+
+/// Upper bound on how long `select_gateway_for_job_id` waits on a single
+/// `gateway_epoch_state_ready` notification before re-checking
+/// `gateway_epoch_state` itself. The wait ends early only when
+/// `notify_cycle_ready` is called; the epoch-state service does not call it,
+/// so without another caller this is the re-check interval.
+pub const GATEWAY_EPOCH_STATE_NOTIFY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often a gateway refreshes its response-lease heartbeat, and how
+/// often `response_lease_sweeper` scans for leases that have gone stale.
+pub const LEASE_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How long a response lease may go without a heartbeat before
+/// `response_lease_sweeper` treats its owning gateway as dead and
+/// reassigns the job. Independent of, and much shorter than, the
+/// `RetryPolicy`-governed response slash deadline, so a crashed gateway's
+/// jobs get picked up long before that would otherwise notice.
+pub const LEASE_TTL: Duration = Duration::from_secs(15);
+
+/// How often `rotate_gas_wallet` re-polls the job store while quiescing a
+/// rotation, waiting for every relay this gateway submitted under the
+/// outgoing key (a job with a recorded relay submission) to reach finality
+/// or be cleared for rebroadcast.
+pub const GAS_WALLET_ROTATION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Confirmations a submitted `relayJob` transaction must accumulate on the
+/// Common Chain, with its `JobRelayed` log still present, before
+/// `RelayFinalityEventuality` treats it as settled rather than still
+/// reorg-able. Reuses the same depth as incoming Common Chain logs since
+/// there's only one chain's finality characteristics to account for here.
+pub const RELAY_FINALITY_CONFIRMATION_DEPTH: u64 = COMMON_CHAIN_CONFIRMATION_DEPTH;
+
+/// Upper bound on how long `RelayFinalityEventuality` waits for a relay
+/// transaction to reach [`RELAY_FINALITY_CONFIRMATION_DEPTH`] before giving
+/// up and re-relaying anyway, as a fallback for the case where neither a
+/// receipt nor a clean reorg ever shows up (e.g. the chain stalls).
+pub const RELAY_FINALITY_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// How long a request chain's block-subscription task waits for a new
+/// block before treating the active WebSocket endpoint as stale and
+/// failing over to the next one in `ws_rpc_urls`, even though the
+/// connection itself hasn't errored.
+pub const STALE_BLOCK_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Backoff between reconnect attempts in `connect_req_chain_ws` when an
+/// endpoint refuses the connection or subscription outright, so a
+/// persistently-down provider doesn't spin the reconnect loop.
+pub const RPC_FAILOVER_RECONNECT_BACKOFF: Duration = Duration::from_secs(3);
+
 lazy_static! {
     pub static ref MIN_GATEWAY_STAKE: U256 = U256::from(111_111_111_111_111_110_000 as u128);
     pub static ref GATEWAY_STAKE_ADJUSTMENT_FACTOR: U256 = U256::from(1e18 as u128);
@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// One event or job this gateway gave up on instead of panicking or
+/// silently dropping, kept around for later inspection/replay. Mirrors the
+/// `StateStore`/`JobStore` split between an in-memory and a SQLite-backed
+/// implementation.
+#[async_trait::async_trait]
+pub trait DeadLetterStore: Send + Sync {
+    /// Persist `entry`, appending it to whatever's already recorded.
+    async fn record(&self, entry: DeadLetterEntry) -> Result<()>;
+
+    /// Every entry recorded so far, oldest first.
+    async fn list(&self) -> Result<Vec<DeadLetterEntry>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct DeadLetterEntry {
+    /// `GatewayError::code()` of the error that caused this entry, so
+    /// entries can be filtered by reason without parsing `message`.
+    pub error_code: String,
+    pub message: String,
+    /// Hex-encoded raw log data (or other offending payload), kept around
+    /// so the entry can be re-decoded and replayed once the root cause is
+    /// fixed.
+    pub raw: String,
+    pub occurred_at: u64,
+}
+
+pub struct MemoryDeadLetterStore {
+    entries: RwLock<Vec<DeadLetterEntry>>,
+}
+
+impl MemoryDeadLetterStore {
+    pub fn new() -> Self {
+        MemoryDeadLetterStore {
+            entries: RwLock::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DeadLetterStore for MemoryDeadLetterStore {
+    async fn record(&self, entry: DeadLetterEntry) -> Result<()> {
+        self.entries.write().await.push(entry);
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<DeadLetterEntry>> {
+        Ok(self.entries.read().await.clone())
+    }
+}
+
+pub struct SqliteDeadLetterStore {
+    conn: tokio::sync::Mutex<rusqlite::Connection>,
+}
+
+impl SqliteDeadLetterStore {
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = rusqlite::Connection::open(path)
+            .context("failed to open SQLite database for the dead-letter store")?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS dead_letters (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                error_code TEXT NOT NULL,
+                message TEXT NOT NULL,
+                raw TEXT NOT NULL,
+                occurred_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        Ok(SqliteDeadLetterStore {
+            conn: tokio::sync::Mutex::new(conn),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl DeadLetterStore for SqliteDeadLetterStore {
+    async fn record(&self, entry: DeadLetterEntry) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT INTO dead_letters (error_code, message, raw, occurred_at) VALUES (?1, ?2, ?3, ?4)",
+            rusqlite::params![entry.error_code, entry.message, entry.raw, entry.occurred_at as i64],
+        )?;
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<DeadLetterEntry>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT error_code, message, raw, occurred_at FROM dead_letters ORDER BY id ASC",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(DeadLetterEntry {
+                    error_code: row.get(0)?,
+                    message: row.get(1)?,
+                    raw: row.get(2)?,
+                    occurred_at: row.get::<_, i64>(3)? as u64,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(rows)
+    }
+}
+
+/// Build the backend named by `kind`: "sqlite" (persisted at `path`) or
+/// "memory". Any other `kind` is an error.
+pub fn open_dead_letter_store(kind: &str, path: &Path) -> Result<Arc<dyn DeadLetterStore>> {
+    match kind {
+        "sqlite" => Ok(Arc::new(SqliteDeadLetterStore::open(path)?)),
+        "memory" => Ok(Arc::new(MemoryDeadLetterStore::new())),
+        other => anyhow::bail!("unknown dead_letter_store backend: {}", other),
+    }
+}
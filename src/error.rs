@@ -0,0 +1,76 @@
+use std::fmt;
+
+use ethers::abi::Error as AbiError;
+use ethers::types::U256;
+
+/// Error taxonomy for the Common Chain job-relay path, modeled on pict-rs's
+/// `UploadError`/`ErrorCode` split: every variant carries a stable `code()`
+/// independent of its `Display` text, so a malformed or out-of-order event
+/// can be logged and dead-lettered with a machine-matchable reason instead
+/// of propagating an opaque `anyhow::Error` or panicking.
+#[derive(Debug)]
+pub enum GatewayError {
+    /// A `JobResponded`/`JobResourceUnavailable` log failed to decode, or
+    /// decoded into tokens of the wrong shape. `raw` is the log's raw data
+    /// hex-encoded, kept around so the dead-letter entry can be replayed
+    /// once the decoding bug (or the upstream contract mismatch) is fixed.
+    InvalidEvent { source: AbiError, raw: String },
+    /// An event referenced a `job_id` this gateway has no record of in the
+    /// `JobStore`, e.g. it was already completed, or this gateway never saw
+    /// the originating `JobRelayed` event.
+    MissingActiveJob(U256),
+    /// A job response reached a path that requires a resolved gateway
+    /// address (e.g. `job_responded_handler`, which unwraps it) without one
+    /// on hand, e.g. because the on-chain re-fetch used to recover it after
+    /// a slash-timer timeout itself failed or timed out.
+    MissingGatewayAddress(U256),
+    /// The `JobStore` itself failed (e.g. the underlying SQLite connection
+    /// errored) while servicing a lookup or write this path needed.
+    JobStore(anyhow::Error),
+    /// An RPC/provider call this path depends on failed or timed out.
+    Provider(anyhow::Error),
+}
+
+impl GatewayError {
+    /// Stable, machine-matchable identifier for structured logging and
+    /// dead-letter records, independent of the (possibly multi-line)
+    /// `Display` text.
+    pub fn code(&self) -> &'static str {
+        match self {
+            GatewayError::InvalidEvent { .. } => "invalid_event",
+            GatewayError::MissingActiveJob(_) => "missing_active_job",
+            GatewayError::MissingGatewayAddress(_) => "missing_gateway_address",
+            GatewayError::JobStore(_) => "job_store",
+            GatewayError::Provider(_) => "provider",
+        }
+    }
+}
+
+impl fmt::Display for GatewayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GatewayError::InvalidEvent { source, .. } => {
+                write!(f, "failed to decode event: {}", source)
+            }
+            GatewayError::MissingActiveJob(job_id) => {
+                write!(f, "job ID {} not found in job store", job_id)
+            }
+            GatewayError::MissingGatewayAddress(job_id) => {
+                write!(f, "no gateway address available for job ID {}", job_id)
+            }
+            GatewayError::JobStore(err) => write!(f, "job store error: {}", err),
+            GatewayError::Provider(err) => write!(f, "provider error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for GatewayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GatewayError::InvalidEvent { source, .. } => Some(source),
+            GatewayError::MissingActiveJob(_) => None,
+            GatewayError::MissingGatewayAddress(_) => None,
+            GatewayError::JobStore(err) | GatewayError::Provider(err) => err.source(),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use log::error;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+use tokio::time;
+
+/// How often the tracker re-checks every still-pending eventuality.
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Outcome of checking the on-chain state an `Eventuality` is waiting on.
+/// Replaces the ad-hoc "are all these fields non-default" boolean checks
+/// that used to be inlined at each call site.
+pub enum Completion {
+    /// The expected on-chain effect (relay, response, ...) has landed.
+    Completed,
+    /// Still waiting; re-check again before `deadline()`.
+    Pending,
+    /// Overtaken by events (e.g. the retry number on chain has moved past
+    /// what this eventuality was watching for) — drop it without slashing.
+    Superseded,
+}
+
+/// Something the gateway is waiting to see confirmed on-chain before a
+/// deadline, with a fallback action to take if the deadline passes first.
+/// Adapted from the Eventuality/confirm_completion split in the Serai
+/// Ethereum processor: one poller drives many eventualities instead of one
+/// `tokio::time::sleep` task per job.
+#[async_trait::async_trait]
+pub trait Eventuality: Send + Sync {
+    /// Check the on-chain state this eventuality is waiting on.
+    async fn confirm_completion(&self) -> Result<Completion>;
+
+    /// Unix timestamp after which a still-`Pending` eventuality times out.
+    fn deadline(&self) -> u64;
+
+    /// Called exactly once, when `deadline` has passed and
+    /// `confirm_completion` is still returning `Pending` or an error: slash the
+    /// assigned gateway and re-enqueue the underlying job/response.
+    async fn on_timeout(self: Box<Self>);
+}
+
+/// Polls every tracked `Eventuality` on a fixed interval instead of each one
+/// sleeping on its own task, so the relay-slash and response-slash timeout
+/// paths share one piece of machinery.
+pub struct EventualityTracker {
+    pending: Mutex<Vec<Box<dyn Eventuality>>>,
+}
+
+impl EventualityTracker {
+    pub fn new() -> Self {
+        EventualityTracker {
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Start watching `eventuality` for completion or timeout.
+    pub async fn track(&self, eventuality: Box<dyn Eventuality>) {
+        self.pending.lock().await.push(eventuality);
+    }
+
+    /// Check every tracked eventuality once: drop the completed and
+    /// superseded ones, and fire the timeout action for any pending one
+    /// whose deadline has passed.
+    async fn poll_once(&self) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let tracked = std::mem::take(&mut *self.pending.lock().await);
+        let mut still_pending = Vec::with_capacity(tracked.len());
+        let mut timed_out = Vec::new();
+
+        for eventuality in tracked {
+            match eventuality.confirm_completion().await {
+                Ok(Completion::Completed) | Ok(Completion::Superseded) => {}
+                Ok(Completion::Pending) => {
+                    if eventuality.deadline() <= now {
+                        timed_out.push(eventuality);
+                    } else {
+                        still_pending.push(eventuality);
+                    }
+                }
+                Err(err) => {
+                    error!("Failed to confirm eventuality completion: {:?}", err);
+                    // A persistently-erroring confirm_completion (e.g. a
+                    // downed provider) must not block the deadline from ever
+                    // firing: that would silently break the bounded-wait
+                    // guarantee this tracker exists to provide.
+                    if eventuality.deadline() <= now {
+                        timed_out.push(eventuality);
+                    } else {
+                        still_pending.push(eventuality);
+                    }
+                }
+            }
+        }
+
+        self.pending.lock().await.extend(still_pending);
+
+        for eventuality in timed_out {
+            eventuality.on_timeout().await;
+        }
+    }
+
+    /// Drive `poll_once` forever. Intended to be run as a single background
+    /// task for the lifetime of the gateway.
+    pub async fn run(self: std::sync::Arc<Self>) {
+        loop {
+            self.poll_once().await;
+            time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use anyhow::{Context, Result};
+use ethers::providers::Middleware;
+use ethers::types::U256;
+
+/// Multiplier, priority-fee floor and absolute max-fee ceiling governing
+/// one chain's fee estimates. Kept separate per request chain (and once
+/// more for the common chain) since congestion and fee conventions differ
+/// chain to chain, e.g. an L2 that needs a much lower ceiling than mainnet.
+#[derive(Debug, Clone)]
+pub struct GasOracleConfig {
+    /// Factor applied to the sampled `max_fee_per_gas`/`max_priority_fee_per_gas`
+    /// before clamping, so operators can pad for volatility without
+    /// recompiling. `1.0` reproduces the raw sampled fees unchanged.
+    pub multiplier: f64,
+    /// Minimum `max_priority_fee_per_gas` offered regardless of what was
+    /// sampled, so a quiet mempool doesn't starve a transaction of any tip.
+    pub priority_fee_floor: U256,
+    /// Absolute ceiling neither fee may exceed regardless of congestion.
+    pub max_fee_cap: U256,
+}
+
+/// Samples a chain's current EIP-1559 fees and scales them by a
+/// `GasOracleConfig`, in place of ethers' unscaled default estimate, so a
+/// congested chain doesn't underprice a submission and stall it.
+pub struct GasOracle {
+    config: GasOracleConfig,
+}
+
+impl GasOracle {
+    pub fn new(config: GasOracleConfig) -> Self {
+        GasOracle { config }
+    }
+
+    /// `(max_fee_per_gas, max_priority_fee_per_gas)` for the next
+    /// transaction on `provider`'s chain: sampled from `eth_feeHistory`
+    /// (falling back to `eth_gasPrice` if the node doesn't support it),
+    /// scaled by `multiplier`, and clamped to `priority_fee_floor`/
+    /// `max_fee_cap`.
+    pub async fn estimate_fees<M: Middleware>(&self, provider: &M) -> Result<(U256, U256)> {
+        let (sampled_max_fee, sampled_priority_fee) = match provider.estimate_eip1559_fees(None).await {
+            Ok(fees) => fees,
+            Err(_) => {
+                let gas_price = provider
+                    .get_gas_price()
+                    .await
+                    .context("failed to fetch eth_gasPrice fallback")?;
+                (gas_price, U256::zero())
+            }
+        };
+
+        let max_fee_per_gas = scale(sampled_max_fee, self.config.multiplier).min(self.config.max_fee_cap);
+        let max_priority_fee_per_gas = scale(sampled_priority_fee, self.config.multiplier)
+            .max(self.config.priority_fee_floor)
+            .min(max_fee_per_gas);
+
+        Ok((max_fee_per_gas, max_priority_fee_per_gas))
+    }
+}
+
+/// Scale `value` by `multiplier` via a basis-points factor, so a fractional
+/// multiplier (e.g. `1.25`) applies to the full 256-bit value without
+/// round-tripping it through a lossy `f64`.
+fn scale(value: U256, multiplier: f64) -> U256 {
+    let basis_points = (multiplier.max(0.0) * 10_000.0).round() as u64;
+    value * U256::from(basis_points) / U256::from(10_000u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_applies_fractional_multiplier_exactly() {
+        assert_eq!(scale(U256::from(1_000u64), 1.25), U256::from(1_250u64));
+        assert_eq!(scale(U256::from(1_000u64), 1.0), U256::from(1_000u64));
+        assert_eq!(scale(U256::from(1_000u64), 0.5), U256::from(500u64));
+    }
+
+    #[test]
+    fn scale_clamps_negative_multiplier_to_zero() {
+        assert_eq!(scale(U256::from(1_000u64), -1.0), U256::zero());
+    }
+
+    #[test]
+    fn scale_handles_values_well_beyond_u64() {
+        let value = U256::from(1u64) << 200;
+        assert_eq!(scale(value, 1.0), value);
+        assert_eq!(scale(value, 0.5), value / 2);
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use ethers::providers::Middleware;
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::Address;
+
+use crate::gas_oracle::GasOracle;
+use crate::nonce_manager::NonceManager;
+use crate::provider_stack::ProviderStack;
+
+/// The gas-paying signer and everything derived from its address: a nonce
+/// baseline and the gas-oracle/nonce-manager stack built on top of it.
+/// Bundled into one struct so rotating the key swaps all three atomically
+/// instead of, say, installing a new signer while stale nonces from the old
+/// address's counter are still in flight.
+#[derive(Clone)]
+pub struct GasWallet {
+    pub signer: LocalWallet,
+    pub address: Address,
+    pub provider_stack: Arc<ProviderStack>,
+}
+
+impl GasWallet {
+    /// Derive a fresh nonce baseline for `signer`'s address from `provider`
+    /// and pair it with `gas_oracle` to build the stack every submission on
+    /// this chain prepares its transaction through.
+    pub async fn new<M: Middleware>(
+        provider: &M,
+        signer: LocalWallet,
+        gas_oracle: Arc<GasOracle>,
+    ) -> Result<Self> {
+        let address = signer.address();
+        let nonce_manager = Arc::new(
+            NonceManager::new(provider, address)
+                .await
+                .context("failed to initialize nonce manager for gas wallet")?,
+        );
+        Ok(GasWallet {
+            signer,
+            address,
+            provider_stack: Arc::new(ProviderStack::new(gas_oracle, nonce_manager)),
+        })
+    }
+}
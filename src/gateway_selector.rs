@@ -0,0 +1,266 @@
+use anyhow::Result;
+use ethers::types::{Address, U256};
+use log::info;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::common_chain_gateway_state_service::GatewayData;
+
+/// Chooses which gateway a job (or job retry) gets assigned to. Extracted
+/// from the stake-weighted-random logic that used to be hard-coded in
+/// `select_gateway_for_job_id`, mirroring the Scheduler-behind-a-trait split
+/// the Serai Ethereum processor uses, so operators can swap assignment
+/// policy without touching the relay core.
+#[async_trait::async_trait]
+pub trait GatewaySelector: Send + Sync {
+    /// Pick the gateway that should relay `job_id`. `gateways` is the full
+    /// gateway set for the current cycle; implementations filter it down to
+    /// `chain_id` themselves, matching the old inline behaviour. `seed` and
+    /// `skips` thread through the per-retry randomness used by callers that
+    /// want a different, deterministic answer on each retry.
+    async fn select_gateway(
+        &self,
+        job_id: U256,
+        seed: u64,
+        skips: u8,
+        gateways: Vec<GatewayData>,
+        chain_id: u64,
+    ) -> Result<Address>;
+
+    /// Record that `gateway` missed its relay-slash deadline for a job, so
+    /// health-aware selectors can deprioritize it on future selections.
+    /// No-op for selectors that don't track gateway health.
+    async fn record_relay_failure(&self, _gateway: Address) {}
+}
+
+fn gateways_for_chain(gateways: &[GatewayData], chain_id: u64) -> Vec<GatewayData> {
+    gateways
+        .iter()
+        .filter(|gateway_data| gateway_data.req_chain_ids.contains(&U256::from(chain_id)))
+        .cloned()
+        .collect()
+}
+
+/// Stake-weighted random selection: build a cumulative `stake_distribution`
+/// over the gateways serving `chain_id`, seed `StdRng` with `seed`, skip
+/// `skips` draws (so retries for the same job land on a different gateway),
+/// then binary-search the cumulative array. This is the selection policy the
+/// gateway previously had hard-coded.
+pub struct StakeWeightedSelector;
+
+impl StakeWeightedSelector {
+    pub fn new() -> Self {
+        StakeWeightedSelector
+    }
+}
+
+#[async_trait::async_trait]
+impl GatewaySelector for StakeWeightedSelector {
+    async fn select_gateway(
+        &self,
+        job_id: U256,
+        seed: u64,
+        skips: u8,
+        gateways: Vec<GatewayData>,
+        chain_id: u64,
+    ) -> Result<Address> {
+        let gateway_data_of_req_chain = gateways_for_chain(&gateways, chain_id);
+        if gateway_data_of_req_chain.is_empty() {
+            anyhow::bail!("no gateways registered for chain {}", chain_id);
+        }
+
+        // create a weighted probability distribution for gateways based on stake amount
+        // For example, if there are 3 gateways with stake amounts 100, 200, 300
+        // then the distribution arrat will be [100, 300, 600]
+        let mut stake_distribution: Vec<u64> = vec![];
+        let mut total_stake: u64 = 0;
+        for gateway_data in gateway_data_of_req_chain.iter() {
+            total_stake += gateway_data.stake_amount.as_u64();
+            stake_distribution.push(total_stake);
+        }
+
+        // random number between 1 to total_stake from the seed for the weighted random selection.
+        // use this seed in std_rng to generate a random number between 1 to total_stake
+        // skipping skips numbers from the random number generated
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0..skips {
+            let _ = rng.gen_range(1..=total_stake);
+        }
+        let random_number = rng.gen_range(1..=total_stake);
+
+        // select the gateway based on the random number
+        let res = stake_distribution.binary_search_by(|&probe| {
+            if probe < random_number {
+                std::cmp::Ordering::Less
+            } else {
+                std::cmp::Ordering::Greater
+            }
+        });
+        let index = match res {
+            Ok(index) => index,
+            Err(index) => index,
+        };
+        let selected_gateway = &gateway_data_of_req_chain[index];
+
+        info!(
+            "Job ID: {:?}, Gateway Address: {:?}",
+            job_id, selected_gateway.address
+        );
+
+        Ok(selected_gateway.address)
+    }
+}
+
+/// Cycles through the gateways serving a chain in a fixed order instead of
+/// weighting by stake. Each call takes the gateway at the current position of
+/// a cursor shared by all jobs, then advances the cursor by `skips + 1`; so
+/// `skips` shifts later selections, not this one, and which gateway a retry
+/// gets depends on how many other selections ran in between.
+pub struct RoundRobinSelector {
+    cursor: AtomicUsize,
+}
+
+impl RoundRobinSelector {
+    pub fn new() -> Self {
+        RoundRobinSelector {
+            cursor: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl GatewaySelector for RoundRobinSelector {
+    async fn select_gateway(
+        &self,
+        job_id: U256,
+        _seed: u64,
+        skips: u8,
+        gateways: Vec<GatewayData>,
+        chain_id: u64,
+    ) -> Result<Address> {
+        let mut gateway_data_of_req_chain = gateways_for_chain(&gateways, chain_id);
+        if gateway_data_of_req_chain.is_empty() {
+            anyhow::bail!("no gateways registered for chain {}", chain_id);
+        }
+        gateway_data_of_req_chain.sort_by_key(|gateway_data| gateway_data.address);
+
+        let advance = skips as usize + 1;
+        let index = self.cursor.fetch_add(advance, Ordering::Relaxed) % gateway_data_of_req_chain.len();
+        let selected_gateway = &gateway_data_of_req_chain[index];
+
+        info!(
+            "Job ID: {:?}, Gateway Address: {:?}",
+            job_id, selected_gateway.address
+        );
+
+        Ok(selected_gateway.address)
+    }
+}
+
+/// Stake-weighted selection that deprioritizes gateways which recently
+/// missed a relay-slash deadline, so a flaky gateway doesn't keep getting
+/// reassigned the same job it's already failed to relay. Failure counts are
+/// capped at `FAILURE_MEMORY` and never decay or reset: a gateway with any
+/// recorded failure is skipped for as long as some gateway serving the chain
+/// has none.
+pub struct LatencyAwareSelector {
+    failures: RwLock<HashMap<Address, u32>>,
+}
+
+const FAILURE_MEMORY: u32 = 3;
+
+impl LatencyAwareSelector {
+    pub fn new() -> Self {
+        LatencyAwareSelector {
+            failures: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl GatewaySelector for LatencyAwareSelector {
+    async fn select_gateway(
+        &self,
+        job_id: U256,
+        seed: u64,
+        skips: u8,
+        gateways: Vec<GatewayData>,
+        chain_id: u64,
+    ) -> Result<Address> {
+        let gateway_data_of_req_chain = gateways_for_chain(&gateways, chain_id);
+        if gateway_data_of_req_chain.is_empty() {
+            anyhow::bail!("no gateways registered for chain {}", chain_id);
+        }
+
+        let failures = self.failures.read().await;
+        // prefer gateways with no recent relay failures; fall back to the
+        // full set if every gateway serving this chain has recently failed.
+        let healthy: Vec<GatewayData> = gateway_data_of_req_chain
+            .iter()
+            .filter(|gateway_data| {
+                failures.get(&gateway_data.address).copied().unwrap_or(0) == 0
+            })
+            .cloned()
+            .collect();
+        let candidates = if healthy.is_empty() {
+            gateway_data_of_req_chain
+        } else {
+            healthy
+        };
+        drop(failures);
+
+        let mut stake_distribution: Vec<u64> = vec![];
+        let mut total_stake: u64 = 0;
+        for gateway_data in candidates.iter() {
+            total_stake += gateway_data.stake_amount.as_u64();
+            stake_distribution.push(total_stake);
+        }
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0..skips {
+            let _ = rng.gen_range(1..=total_stake);
+        }
+        let random_number = rng.gen_range(1..=total_stake);
+
+        let res = stake_distribution.binary_search_by(|&probe| {
+            if probe < random_number {
+                std::cmp::Ordering::Less
+            } else {
+                std::cmp::Ordering::Greater
+            }
+        });
+        let index = match res {
+            Ok(index) => index,
+            Err(index) => index,
+        };
+        let selected_gateway = &candidates[index];
+
+        info!(
+            "Job ID: {:?}, Gateway Address: {:?}",
+            job_id, selected_gateway.address
+        );
+
+        Ok(selected_gateway.address)
+    }
+
+    async fn record_relay_failure(&self, gateway: Address) {
+        let mut failures = self.failures.write().await;
+        let count = failures.entry(gateway).or_insert(0);
+        *count = (*count + 1).min(FAILURE_MEMORY);
+    }
+}
+
+/// Build the selector named by `kind`: "stake_weighted", "round_robin" or
+/// "latency_aware". Any other `kind` is an error.
+pub fn build_gateway_selector(kind: &str) -> Result<Arc<dyn GatewaySelector>> {
+    match kind {
+        "stake_weighted" => Ok(Arc::new(StakeWeightedSelector::new())),
+        "round_robin" => Ok(Arc::new(RoundRobinSelector::new())),
+        "latency_aware" => Ok(Arc::new(LatencyAwareSelector::new())),
+        other => anyhow::bail!("unknown gateway_selector backend: {}", other),
+    }
+}
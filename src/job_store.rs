@@ -0,0 +1,814 @@
+use anyhow::{Context, Result};
+use ethers::types::{Address, Bytes, FixedBytes, H256, U256};
+use rusqlite::OptionalExtension;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::model::{ComChainJobType, Job, JobResponse, ReqChainJobType};
+
+/// The transaction hash and block a `relayJob` submission landed in,
+/// recorded so `RelayFinalityEventuality` can resume watching it for
+/// finality (or a reorg) after a restart instead of losing track of an
+/// in-flight submission.
+#[derive(Debug, Clone, Copy)]
+pub struct RelaySubmission {
+    pub tx_hash: H256,
+    pub submitted_block: u64,
+}
+
+/// Lifecycle of a `job_queue` row. A job starts `New` once `push`ed and is
+/// flipped to `Running` by `pop_pending`, or by `mark_running` just before a
+/// response transaction for it is submitted. Only `pop_pending` reads the
+/// status; `info` and `list_active` return jobs whatever their status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    New,
+    Running,
+}
+
+/// Backing store for in-flight jobs and their in-flight responses, so a
+/// process restart can replay `list_active` and resume relay/slash tracking
+/// instead of silently dropping everything a crash interrupted. Mirrors the
+/// `StateStore` split between an in-memory and a SQLite-backed
+/// implementation.
+#[async_trait::async_trait]
+pub trait JobStore: Send + Sync {
+    /// Queue `job` for tracking in `New` status, overwriting any previous
+    /// entry for `job.job_id` (e.g. a retry re-pushing under the same ID).
+    async fn push(&self, job: Job) -> Result<()>;
+
+    /// Atomically claim one `New` job, flipping it to `Running` and
+    /// returning it, or `None` if nothing is queued. Which queued job is
+    /// claimed first is not defined.
+    async fn pop_pending(&self) -> Result<Option<Job>>;
+
+    /// Look up one job by ID regardless of status.
+    async fn info(&self, job_id: U256) -> Result<Option<Job>>;
+
+    /// Record that a response transaction for `job_id` is being submitted,
+    /// flipping its stored status to `Running`. Called by `job_response_txn`
+    /// before it sends; restart rehydration does not read the status.
+    async fn mark_running(&self, job_id: U256) -> Result<()>;
+
+    /// Mark a job done, dropping it if `retry_number` still matches what's
+    /// stored (the guard formerly inlined at each removal call site); a
+    /// no-op if a later retry has already superseded it.
+    async fn complete(&self, job_id: U256, retry_number: u8) -> Result<()>;
+
+    /// Every job not yet completed. Replayed on startup to resume
+    /// relay/slash tracking for jobs that were mid-flight.
+    async fn list_active(&self) -> Result<Vec<Job>>;
+
+    /// Record that `runner_id` has taken ownership of submitting
+    /// `job_response`'s output, for lease-based liveness tracking
+    /// independent of (and much shorter than) the `JobResponseEventuality`
+    /// slash deadline.
+    async fn acquire_response_lease(
+        &self,
+        job_response: &JobResponse,
+        runner_id: &str,
+        now: u64,
+    ) -> Result<()>;
+
+    /// Refresh the heartbeat of an existing response lease. Returns
+    /// `false` if no lease for `job_id` held by `runner_id` exists any
+    /// more (e.g. it was already reclaimed), signalling the caller to stop
+    /// heartbeating.
+    async fn heartbeat_response_lease(&self, job_id: U256, runner_id: &str, now: u64) -> Result<bool>;
+
+    /// Every leased response whose heartbeat is older than `lease_ttl`
+    /// seconds before `now`, i.e. whose owning runner appears to have
+    /// died. Clears each lease returned so it isn't reclaimed twice.
+    async fn reclaim_expired_response_leases(
+        &self,
+        lease_ttl: u64,
+        now: u64,
+    ) -> Result<Vec<JobResponse>>;
+
+    /// Drop a response lease once its job response has been confirmed.
+    async fn release_response_lease(&self, job_id: U256) -> Result<()>;
+
+    /// Record that `job_id`'s `relayJob` transaction was submitted and
+    /// mined as `tx_hash` at `submitted_block`, overwriting any previous
+    /// submission recorded for the same job (e.g. a prior attempt that was
+    /// reorged out). Watched by `RelayFinalityEventuality` until it clears
+    /// the finality confirmation depth.
+    async fn record_relay_submission(
+        &self,
+        job_id: U256,
+        tx_hash: H256,
+        submitted_block: u64,
+    ) -> Result<()>;
+
+    /// The relay submission recorded for `job_id`, if any, so restart
+    /// recovery can resume finality tracking instead of starting blind.
+    async fn relay_submission(&self, job_id: U256) -> Result<Option<RelaySubmission>>;
+
+    /// Drop `job_id`'s recorded relay submission once it's finalized or
+    /// superseded by a fresh re-relay attempt.
+    async fn clear_relay_submission(&self, job_id: U256) -> Result<()>;
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredJob {
+    job_id: [u64; 4],
+    req_chain_id: u64,
+    tx_hash: Vec<u8>,
+    code_input: Vec<u8>,
+    user_timout: [u64; 4],
+    starttime: [u64; 4],
+    max_gas_price: [u64; 4],
+    deposit: [u8; 20],
+    callback_deposit: [u64; 4],
+    job_owner: [u8; 20],
+    job_type: u8,
+    retry_number: u8,
+    gateway_address: Option<[u8; 20]>,
+}
+
+fn encode_job_type(job_type: &ComChainJobType) -> u8 {
+    match job_type {
+        ComChainJobType::JobRelay => 0,
+        ComChainJobType::SlashGatewayJob => 1,
+    }
+}
+
+fn decode_job_type(job_type: u8) -> ComChainJobType {
+    match job_type {
+        1 => ComChainJobType::SlashGatewayJob,
+        _ => ComChainJobType::JobRelay,
+    }
+}
+
+fn encode_status(status: JobStatus) -> i64 {
+    match status {
+        JobStatus::New => 0,
+        JobStatus::Running => 1,
+    }
+}
+
+impl StoredJob {
+    fn from_job(job: &Job) -> Self {
+        StoredJob {
+            job_id: job.job_id.0,
+            req_chain_id: job.req_chain_id,
+            tx_hash: job.tx_hash.to_vec(),
+            code_input: job.code_input.to_vec(),
+            user_timout: job.user_timout.0,
+            starttime: job.starttime.0,
+            max_gas_price: job.max_gas_price.0,
+            deposit: job.deposit.0,
+            callback_deposit: job.callback_deposit.0,
+            job_owner: job.job_owner.0,
+            job_type: encode_job_type(&job.job_type),
+            retry_number: job.retry_number,
+            gateway_address: job.gateway_address.map(|address| address.0),
+        }
+    }
+
+    fn into_job(self) -> Job {
+        Job {
+            job_id: U256(self.job_id),
+            req_chain_id: self.req_chain_id,
+            tx_hash: FixedBytes::from(self.tx_hash),
+            code_input: Bytes::from(self.code_input),
+            user_timout: U256(self.user_timout),
+            starttime: U256(self.starttime),
+            max_gas_price: U256(self.max_gas_price),
+            deposit: Address(self.deposit),
+            callback_deposit: U256(self.callback_deposit),
+            job_owner: Address(self.job_owner),
+            job_type: decode_job_type(self.job_type),
+            retry_number: self.retry_number,
+            gateway_address: self.gateway_address.map(Address),
+        }
+    }
+}
+
+fn encode_req_job_type(job_type: &ReqChainJobType) -> u8 {
+    match job_type {
+        ReqChainJobType::JobResponded => 0,
+        ReqChainJobType::SlashGatewayResponse => 1,
+    }
+}
+
+fn decode_req_job_type(job_type: u8) -> ReqChainJobType {
+    match job_type {
+        1 => ReqChainJobType::SlashGatewayResponse,
+        _ => ReqChainJobType::JobResponded,
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredJobResponse {
+    job_id: [u64; 4],
+    req_chain_id: [u64; 4],
+    output: Vec<u8>,
+    total_time: [u64; 4],
+    error_code: u8,
+    output_count: u8,
+    job_type: u8,
+    gateway_address: Option<[u8; 20]>,
+    retry_number: u8,
+    next_attempt_at: u64,
+}
+
+impl StoredJobResponse {
+    fn from_job_response(job_response: &JobResponse) -> Self {
+        StoredJobResponse {
+            job_id: job_response.job_id.0,
+            req_chain_id: job_response.req_chain_id.0,
+            output: job_response.output.to_vec(),
+            total_time: job_response.total_time.0,
+            error_code: job_response.error_code,
+            output_count: job_response.output_count,
+            job_type: encode_req_job_type(&job_response.job_type),
+            gateway_address: job_response.gateway_address.map(|address| address.0),
+            retry_number: job_response.retry_number,
+            next_attempt_at: job_response.next_attempt_at,
+        }
+    }
+
+    fn into_job_response(self) -> JobResponse {
+        JobResponse {
+            job_id: U256(self.job_id),
+            req_chain_id: U256(self.req_chain_id),
+            output: Bytes::from(self.output),
+            total_time: U256(self.total_time),
+            error_code: self.error_code,
+            output_count: self.output_count,
+            job_type: decode_req_job_type(self.job_type),
+            gateway_address: self.gateway_address.map(Address),
+            retry_number: self.retry_number,
+            next_attempt_at: self.next_attempt_at,
+        }
+    }
+}
+
+/// Keeps every in-flight job and response lease in memory only, matching
+/// the behaviour before a persistent backend existed. Built by
+/// `open_job_store` for the "memory" kind.
+pub struct MemoryJobStore {
+    jobs: RwLock<BTreeMap<U256, (Job, JobStatus)>>,
+    response_leases: RwLock<BTreeMap<U256, (JobResponse, String, u64)>>,
+    relay_submissions: RwLock<BTreeMap<U256, RelaySubmission>>,
+}
+
+impl MemoryJobStore {
+    pub fn new() -> Self {
+        MemoryJobStore {
+            jobs: RwLock::new(BTreeMap::new()),
+            response_leases: RwLock::new(BTreeMap::new()),
+            relay_submissions: RwLock::new(BTreeMap::new()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl JobStore for MemoryJobStore {
+    async fn push(&self, job: Job) -> Result<()> {
+        self.jobs
+            .write()
+            .await
+            .insert(job.job_id, (job, JobStatus::New));
+        Ok(())
+    }
+
+    async fn pop_pending(&self) -> Result<Option<Job>> {
+        let mut jobs = self.jobs.write().await;
+        let Some(job_id) = jobs
+            .iter()
+            .find(|(_, (_, status))| *status == JobStatus::New)
+            .map(|(job_id, _)| *job_id)
+        else {
+            return Ok(None);
+        };
+        let entry = jobs.get_mut(&job_id).unwrap();
+        entry.1 = JobStatus::Running;
+        Ok(Some(entry.0.clone()))
+    }
+
+    async fn info(&self, job_id: U256) -> Result<Option<Job>> {
+        Ok(self
+            .jobs
+            .read()
+            .await
+            .get(&job_id)
+            .map(|(job, _)| job.clone()))
+    }
+
+    async fn mark_running(&self, job_id: U256) -> Result<()> {
+        if let Some(entry) = self.jobs.write().await.get_mut(&job_id) {
+            entry.1 = JobStatus::Running;
+        }
+        Ok(())
+    }
+
+    async fn complete(&self, job_id: U256, retry_number: u8) -> Result<()> {
+        let mut jobs = self.jobs.write().await;
+        if jobs
+            .get(&job_id)
+            .is_some_and(|(job, _)| job.retry_number == retry_number)
+        {
+            jobs.remove(&job_id);
+        }
+        Ok(())
+    }
+
+    async fn list_active(&self) -> Result<Vec<Job>> {
+        Ok(self
+            .jobs
+            .read()
+            .await
+            .values()
+            .map(|(job, _)| job.clone())
+            .collect())
+    }
+
+    async fn acquire_response_lease(
+        &self,
+        job_response: &JobResponse,
+        runner_id: &str,
+        now: u64,
+    ) -> Result<()> {
+        self.response_leases.write().await.insert(
+            job_response.job_id,
+            (job_response.clone(), runner_id.to_string(), now),
+        );
+        Ok(())
+    }
+
+    async fn heartbeat_response_lease(&self, job_id: U256, runner_id: &str, now: u64) -> Result<bool> {
+        let mut leases = self.response_leases.write().await;
+        let Some(lease) = leases.get_mut(&job_id) else {
+            return Ok(false);
+        };
+        if lease.1 != runner_id {
+            return Ok(false);
+        }
+        lease.2 = now;
+        Ok(true)
+    }
+
+    async fn reclaim_expired_response_leases(
+        &self,
+        lease_ttl: u64,
+        now: u64,
+    ) -> Result<Vec<JobResponse>> {
+        let mut leases = self.response_leases.write().await;
+        let expired_job_ids: Vec<U256> = leases
+            .iter()
+            .filter(|(_, (_, _, last_heartbeat))| now.saturating_sub(*last_heartbeat) > lease_ttl)
+            .map(|(job_id, _)| *job_id)
+            .collect();
+
+        Ok(expired_job_ids
+            .into_iter()
+            .filter_map(|job_id| leases.remove(&job_id).map(|(job_response, _, _)| job_response))
+            .collect())
+    }
+
+    async fn release_response_lease(&self, job_id: U256) -> Result<()> {
+        self.response_leases.write().await.remove(&job_id);
+        Ok(())
+    }
+
+    async fn record_relay_submission(
+        &self,
+        job_id: U256,
+        tx_hash: H256,
+        submitted_block: u64,
+    ) -> Result<()> {
+        self.relay_submissions.write().await.insert(
+            job_id,
+            RelaySubmission {
+                tx_hash,
+                submitted_block,
+            },
+        );
+        Ok(())
+    }
+
+    async fn relay_submission(&self, job_id: U256) -> Result<Option<RelaySubmission>> {
+        Ok(self.relay_submissions.read().await.get(&job_id).copied())
+    }
+
+    async fn clear_relay_submission(&self, job_id: U256) -> Result<()> {
+        self.relay_submissions.write().await.remove(&job_id);
+        Ok(())
+    }
+}
+
+/// SQLite-backed store so in-flight jobs, in-flight responses, and response
+/// leases survive a process restart.
+pub struct SqliteJobStore {
+    conn: tokio::sync::Mutex<rusqlite::Connection>,
+}
+
+impl SqliteJobStore {
+    pub fn open(path: &Path) -> Result<Self> {
+        let conn = rusqlite::Connection::open(path)
+            .context("failed to open SQLite database for the job store")?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS job_queue (
+                job_id TEXT PRIMARY KEY,
+                req_chain_id INTEGER NOT NULL,
+                retry_number INTEGER NOT NULL,
+                gateway_address TEXT,
+                job_type INTEGER NOT NULL,
+                payload BLOB NOT NULL,
+                status INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS response_leases (
+                job_id TEXT PRIMARY KEY,
+                runner_id TEXT NOT NULL,
+                last_heartbeat INTEGER NOT NULL,
+                payload BLOB NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS relay_submissions (
+                job_id TEXT PRIMARY KEY,
+                tx_hash TEXT NOT NULL,
+                submitted_block INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        Ok(SqliteJobStore {
+            conn: tokio::sync::Mutex::new(conn),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl JobStore for SqliteJobStore {
+    async fn push(&self, job: Job) -> Result<()> {
+        let blob = bincode::serialize(&StoredJob::from_job(&job))?;
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT INTO job_queue (job_id, req_chain_id, retry_number, gateway_address, job_type, payload, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(job_id) DO UPDATE SET
+                req_chain_id = excluded.req_chain_id,
+                retry_number = excluded.retry_number,
+                gateway_address = excluded.gateway_address,
+                job_type = excluded.job_type,
+                payload = excluded.payload,
+                status = excluded.status",
+            rusqlite::params![
+                job.job_id.to_string(),
+                job.req_chain_id,
+                job.retry_number,
+                job.gateway_address.map(|address| format!("{:?}", address)),
+                encode_job_type(&job.job_type),
+                blob,
+                encode_status(JobStatus::New),
+            ],
+        )?;
+        Ok(())
+    }
+
+    async fn pop_pending(&self) -> Result<Option<Job>> {
+        let conn = self.conn.lock().await;
+        let job_id: Option<String> = conn
+            .query_row(
+                "SELECT job_id FROM job_queue WHERE status = ?1 LIMIT 1",
+                rusqlite::params![encode_status(JobStatus::New)],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(job_id) = job_id else {
+            return Ok(None);
+        };
+        conn.execute(
+            "UPDATE job_queue SET status = ?1 WHERE job_id = ?2",
+            rusqlite::params![encode_status(JobStatus::Running), job_id],
+        )?;
+        let blob: Vec<u8> = conn.query_row(
+            "SELECT payload FROM job_queue WHERE job_id = ?1",
+            rusqlite::params![job_id],
+            |row| row.get(0),
+        )?;
+        let stored: StoredJob = bincode::deserialize(&blob)?;
+        Ok(Some(stored.into_job()))
+    }
+
+    async fn info(&self, job_id: U256) -> Result<Option<Job>> {
+        let conn = self.conn.lock().await;
+        let blob: Option<Vec<u8>> = conn
+            .query_row(
+                "SELECT payload FROM job_queue WHERE job_id = ?1",
+                rusqlite::params![job_id.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        blob.map(|blob| Ok(bincode::deserialize::<StoredJob>(&blob)?.into_job()))
+            .transpose()
+    }
+
+    async fn mark_running(&self, job_id: U256) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "UPDATE job_queue SET status = ?1 WHERE job_id = ?2",
+            rusqlite::params![encode_status(JobStatus::Running), job_id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    async fn complete(&self, job_id: U256, retry_number: u8) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "DELETE FROM job_queue WHERE job_id = ?1 AND retry_number = ?2",
+            rusqlite::params![job_id.to_string(), retry_number],
+        )?;
+        Ok(())
+    }
+
+    async fn list_active(&self) -> Result<Vec<Job>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare("SELECT payload FROM job_queue")?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, Vec<u8>>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows.into_iter()
+            .map(|blob| Ok(bincode::deserialize::<StoredJob>(&blob)?.into_job()))
+            .collect()
+    }
+
+    async fn acquire_response_lease(
+        &self,
+        job_response: &JobResponse,
+        runner_id: &str,
+        now: u64,
+    ) -> Result<()> {
+        let blob = bincode::serialize(&StoredJobResponse::from_job_response(job_response))?;
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT INTO response_leases (job_id, runner_id, last_heartbeat, payload)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(job_id) DO UPDATE SET
+                runner_id = excluded.runner_id,
+                last_heartbeat = excluded.last_heartbeat,
+                payload = excluded.payload",
+            rusqlite::params![job_response.job_id.to_string(), runner_id, now as i64, blob],
+        )?;
+        Ok(())
+    }
+
+    async fn heartbeat_response_lease(&self, job_id: U256, runner_id: &str, now: u64) -> Result<bool> {
+        let conn = self.conn.lock().await;
+        let updated = conn.execute(
+            "UPDATE response_leases SET last_heartbeat = ?1 WHERE job_id = ?2 AND runner_id = ?3",
+            rusqlite::params![now as i64, job_id.to_string(), runner_id],
+        )?;
+        Ok(updated > 0)
+    }
+
+    async fn reclaim_expired_response_leases(
+        &self,
+        lease_ttl: u64,
+        now: u64,
+    ) -> Result<Vec<JobResponse>> {
+        let conn = self.conn.lock().await;
+        let cutoff = now as i64 - lease_ttl as i64;
+        let rows = {
+            let mut stmt =
+                conn.prepare("SELECT job_id, payload FROM response_leases WHERE last_heartbeat < ?1")?;
+            stmt.query_map(rusqlite::params![cutoff], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?
+        };
+
+        let mut reclaimed = Vec::with_capacity(rows.len());
+        for (job_id, blob) in rows {
+            conn.execute(
+                "DELETE FROM response_leases WHERE job_id = ?1",
+                rusqlite::params![job_id],
+            )?;
+            reclaimed.push(bincode::deserialize::<StoredJobResponse>(&blob)?.into_job_response());
+        }
+        Ok(reclaimed)
+    }
+
+    async fn release_response_lease(&self, job_id: U256) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "DELETE FROM response_leases WHERE job_id = ?1",
+            rusqlite::params![job_id.to_string()],
+        )?;
+        Ok(())
+    }
+
+    async fn record_relay_submission(
+        &self,
+        job_id: U256,
+        tx_hash: H256,
+        submitted_block: u64,
+    ) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT INTO relay_submissions (job_id, tx_hash, submitted_block)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(job_id) DO UPDATE SET
+                tx_hash = excluded.tx_hash,
+                submitted_block = excluded.submitted_block",
+            rusqlite::params![job_id.to_string(), format!("{:?}", tx_hash), submitted_block as i64],
+        )?;
+        Ok(())
+    }
+
+    async fn relay_submission(&self, job_id: U256) -> Result<Option<RelaySubmission>> {
+        let conn = self.conn.lock().await;
+        let row: Option<(String, i64)> = conn
+            .query_row(
+                "SELECT tx_hash, submitted_block FROM relay_submissions WHERE job_id = ?1",
+                rusqlite::params![job_id.to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        row.map(|(tx_hash, submitted_block)| {
+            Ok(RelaySubmission {
+                tx_hash: tx_hash
+                    .parse()
+                    .context("failed to parse stored relay submission tx_hash")?,
+                submitted_block: submitted_block as u64,
+            })
+        })
+        .transpose()
+    }
+
+    async fn clear_relay_submission(&self, job_id: U256) -> Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "DELETE FROM relay_submissions WHERE job_id = ?1",
+            rusqlite::params![job_id.to_string()],
+        )?;
+        Ok(())
+    }
+}
+
+/// Build the backend named by `kind`: "sqlite" (persisted at `path`) or
+/// "memory". Any other `kind` is an error.
+pub fn open_job_store(kind: &str, path: &Path) -> Result<Arc<dyn JobStore>> {
+    match kind {
+        "sqlite" => Ok(Arc::new(SqliteJobStore::open(path)?)),
+        "memory" => Ok(Arc::new(MemoryJobStore::new())),
+        other => anyhow::bail!("unknown job_store backend: {}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::Not;
+
+    fn sample_job(job_id: u64, retry_number: u8) -> Job {
+        Job {
+            job_id: U256::from(job_id),
+            req_chain_id: 1,
+            tx_hash: vec![0u8; 32],
+            code_input: Bytes::from(vec![1, 2, 3]),
+            user_timout: U256::from(100u64),
+            starttime: U256::from(200u64),
+            max_gas_price: U256::from(300u64),
+            deposit: Address::zero(),
+            callback_deposit: U256::from(400u64),
+            job_owner: Address::zero(),
+            job_type: ComChainJobType::JobRelay,
+            retry_number,
+            gateway_address: None,
+        }
+    }
+
+    fn sample_job_response(job_id: u64, retry_number: u8) -> JobResponse {
+        JobResponse {
+            job_id: U256::from(job_id),
+            req_chain_id: U256::from(1u64),
+            output: Bytes::from(vec![9, 9, 9]),
+            total_time: U256::from(50u64),
+            error_code: 0,
+            output_count: 1,
+            job_type: ReqChainJobType::JobResponded,
+            gateway_address: None,
+            retry_number,
+            next_attempt_at: 12345,
+        }
+    }
+
+    /// Exercises the full `JobStore` contract against whichever backend is
+    /// passed in, so Memory and SQLite are held to the same behavior.
+    async fn exercises_job_lifecycle(store: &dyn JobStore) {
+        assert!(store.info(U256::from(1u64)).await.unwrap().is_none());
+        assert!(store.pop_pending().await.unwrap().is_none());
+
+        store.push(sample_job(1, 0)).await.unwrap();
+        store.push(sample_job(2, 0)).await.unwrap();
+        assert_eq!(store.list_active().await.unwrap().len(), 2);
+        assert_eq!(store.info(U256::from(1u64)).await.unwrap(), Some(sample_job(1, 0)));
+
+        // pop_pending claims the job and flips it to Running, so it is not
+        // handed out a second time.
+        let mut popped = vec![
+            store.pop_pending().await.unwrap().unwrap(),
+            store.pop_pending().await.unwrap().unwrap(),
+        ];
+        popped.sort_by_key(|job| job.job_id);
+        assert_eq!(popped, vec![sample_job(1, 0), sample_job(2, 0)]);
+        assert!(store.pop_pending().await.unwrap().is_none());
+
+        // complete() is a no-op if retry_number no longer matches what's
+        // stored (a later retry already superseded it).
+        store.complete(U256::from(1u64), 5).await.unwrap();
+        assert!(store.info(U256::from(1u64)).await.unwrap().is_some());
+        store.complete(U256::from(1u64), 0).await.unwrap();
+        assert!(store.info(U256::from(1u64)).await.unwrap().is_none());
+
+        // response leases: acquire, heartbeat, reclaim once expired, release.
+        let response = sample_job_response(2, 0);
+        store
+            .acquire_response_lease(&response, "runner-a", 1_000)
+            .await
+            .unwrap();
+        assert!(store
+            .heartbeat_response_lease(U256::from(2u64), "runner-b", 1_001)
+            .await
+            .unwrap()
+            .not());
+        assert!(store
+            .heartbeat_response_lease(U256::from(2u64), "runner-a", 1_001)
+            .await
+            .unwrap());
+
+        let reclaimed = store
+            .reclaim_expired_response_leases(10, 1_005)
+            .await
+            .unwrap();
+        assert!(reclaimed.is_empty(), "lease should not be expired yet");
+
+        let reclaimed = store
+            .reclaim_expired_response_leases(10, 2_000)
+            .await
+            .unwrap();
+        assert_eq!(reclaimed, vec![response]);
+        // already reclaimed, so it isn't handed out a second time
+        assert!(store
+            .reclaim_expired_response_leases(10, 2_000)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let response = sample_job_response(3, 0);
+        store
+            .acquire_response_lease(&response, "runner-a", 1_000)
+            .await
+            .unwrap();
+        store.release_response_lease(U256::from(3u64)).await.unwrap();
+        assert!(store
+            .reclaim_expired_response_leases(0, 1_000)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // relay submission tracking, used to resume RelayFinalityEventuality
+        // after a restart.
+        assert!(store
+            .relay_submission(U256::from(2u64))
+            .await
+            .unwrap()
+            .is_none());
+        let tx_hash = H256::repeat_byte(0x11);
+        store
+            .record_relay_submission(U256::from(2u64), tx_hash, 42)
+            .await
+            .unwrap();
+        let submission = store.relay_submission(U256::from(2u64)).await.unwrap().unwrap();
+        assert_eq!(submission.tx_hash, tx_hash);
+        assert_eq!(submission.submitted_block, 42);
+
+        store.clear_relay_submission(U256::from(2u64)).await.unwrap();
+        assert!(store
+            .relay_submission(U256::from(2u64))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn memory_job_store_lifecycle() {
+        exercises_job_lifecycle(&MemoryJobStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_job_store_lifecycle() {
+        let store = SqliteJobStore::open(Path::new(":memory:")).unwrap();
+        exercises_job_lifecycle(&store).await;
+    }
+}
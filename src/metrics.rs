@@ -0,0 +1,326 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Response, Server};
+use log::{error, info};
+use prometheus::{
+    Encoder, Gauge, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, IntGaugeVec,
+    Opts, Registry, TextEncoder,
+};
+
+/// Prometheus metrics emitted by the epoch-state indexer, modeled on
+/// Garage's `admin/metrics.rs`: one registry shared across every per-cycle
+/// and per-event-type measurement, exposed over `/metrics`.
+#[derive(Clone)]
+pub struct GatewayStateMetrics {
+    registry: Registry,
+    pub current_cycle_number: IntGauge,
+    pub current_cycle_to_block: IntGauge,
+    pub active_gateways: IntGauge,
+    /// Summed stake of active gateways, in whole tokens (stake_amount scaled
+    /// down by `GATEWAY_STAKE_ADJUSTMENT_FACTOR`, i.e. from wei-like base
+    /// units to 1e18 units). An `IntGauge` can't hold this: a single
+    /// gateway's `MIN_GATEWAY_STAKE` already exceeds `i64::MAX` in base
+    /// units, so an unscaled `u128` sum cast to `i64` wraps silently.
+    pub total_stake_amount: Gauge,
+    pub events_processed: IntCounterVec,
+    pub log_fetch_latency: Histogram,
+    pub provider_errors: IntCounterVec,
+}
+
+impl GatewayStateMetrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let current_cycle_number =
+            IntGauge::new("gateway_epoch_current_cycle", "Current epoch cycle number").unwrap();
+        let current_cycle_to_block = IntGauge::new(
+            "gateway_epoch_current_cycle_to_block",
+            "to_block_number of the current epoch cycle",
+        )
+        .unwrap();
+        let active_gateways = IntGauge::new(
+            "gateway_epoch_active_gateways",
+            "Number of active gateways in the current cycle",
+        )
+        .unwrap();
+        let total_stake_amount = Gauge::new(
+            "gateway_epoch_total_stake_amount",
+            "Summed stake_amount of active gateways in the current cycle, in whole tokens (base units scaled down by GATEWAY_STAKE_ADJUSTMENT_FACTOR)",
+        )
+        .unwrap();
+        let events_processed = IntCounterVec::new(
+            Opts::new(
+                "gateway_epoch_events_processed_total",
+                "Number of gateway-contract events processed, by event type",
+            ),
+            &["event_type"],
+        )
+        .unwrap();
+        let log_fetch_latency = Histogram::with_opts(HistogramOpts::new(
+            "gateway_epoch_log_fetch_latency_seconds",
+            "Latency of per-cycle eth_getLogs fetches",
+        ))
+        .unwrap();
+        let provider_errors = IntCounterVec::new(
+            Opts::new(
+                "gateway_epoch_provider_errors_total",
+                "Number of RPC provider errors encountered while indexing, by cause",
+            ),
+            &["cause"],
+        )
+        .unwrap();
+
+        registry
+            .register(Box::new(current_cycle_number.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(current_cycle_to_block.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(active_gateways.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(total_stake_amount.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(events_processed.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(log_fetch_latency.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(provider_errors.clone()))
+            .unwrap();
+
+        GatewayStateMetrics {
+            registry,
+            current_cycle_number,
+            current_cycle_to_block,
+            active_gateways,
+            total_stake_amount,
+            events_processed,
+            log_fetch_latency,
+            provider_errors,
+        }
+    }
+}
+
+async fn serve_req(
+    req: Request<Body>,
+    metrics: GatewayStateMetrics,
+) -> Result<Response<Body>, Infallible> {
+    if req.uri().path() != "/metrics" {
+        return Ok(Response::builder()
+            .status(404)
+            .body(Body::from("not found"))
+            .unwrap());
+    }
+
+    let metric_families = metrics.registry.gather();
+    let mut buffer = vec![];
+    TextEncoder::new()
+        .encode(&metric_families, &mut buffer)
+        .unwrap();
+
+    Ok(Response::new(Body::from(buffer)))
+}
+
+/// Spawn the `/metrics` HTTP endpoint on `addr`, serving the given registry
+/// in Prometheus text format.
+pub async fn serve_metrics(addr: SocketAddr, metrics: GatewayStateMetrics) {
+    let make_svc = make_service_fn(move |_conn| {
+        let metrics = metrics.clone();
+        async move { Ok::<_, Infallible>(service_fn(move |req| serve_req(req, metrics.clone()))) }
+    });
+
+    info!("Serving gateway epoch state metrics on {}", addr);
+    if let Err(err) = Server::bind(&addr).serve(make_svc).await {
+        error!("Metrics server error: {:?}", err);
+    }
+}
+
+/// Prometheus metrics emitted by the Common Chain client: job intake per
+/// request chain, gateway-selection latency, relay/reassign
+/// time-to-confirmation, slash-timer firings, retry-number distribution,
+/// and how long `select_gateway_for_job_id` spends blocked waiting for its
+/// epoch cycle to be populated.
+#[derive(Clone)]
+pub struct GatewayClientMetrics {
+    registry: Registry,
+    pub jobs_received: IntCounterVec,
+    pub gateway_selection_latency: Histogram,
+    pub relay_confirmation_latency: Histogram,
+    pub reassign_confirmation_latency: Histogram,
+    pub slash_timer_firings: IntCounterVec,
+    pub job_retry_number: IntCounterVec,
+    pub gateway_epoch_state_wait_latency: Histogram,
+    /// Number of times a submitted `relayJob` transaction was re-broadcast
+    /// because the confirmed receipt disappeared, reverted, or lost its
+    /// `JobRelayed` log before reaching `RELAY_FINALITY_CONFIRMATION_DEPTH`,
+    /// or because it did not reach that depth within `RELAY_FINALITY_TIMEOUT`.
+    pub relay_finality_rebroadcasts: IntCounter,
+    /// Index into a request chain's `ws_rpc_urls` of the endpoint
+    /// `FailoverEndpoints` is currently active on, by chain ID, so
+    /// operators can see which RPC provider is serving a chain's event
+    /// subscriptions without reading logs.
+    pub req_chain_active_ws_endpoint: IntGaugeVec,
+    /// Consecutive failures recorded against a request chain's active WS
+    /// endpoint, by chain ID. Climbs toward the failover threshold, then
+    /// resets to 0 on the next successful block/log.
+    pub req_chain_ws_endpoint_consecutive_failures: IntGaugeVec,
+}
+
+impl GatewayClientMetrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let jobs_received = IntCounterVec::new(
+            Opts::new(
+                "gateway_client_jobs_received_total",
+                "Number of JobRelayed events received, by request chain ID",
+            ),
+            &["req_chain_id"],
+        )
+        .unwrap();
+        let gateway_selection_latency = Histogram::with_opts(HistogramOpts::new(
+            "gateway_client_gateway_selection_latency_seconds",
+            "Duration of select_gateway_for_job_id, including any epoch-state wait",
+        ))
+        .unwrap();
+        let relay_confirmation_latency = Histogram::with_opts(HistogramOpts::new(
+            "gateway_client_relay_confirmation_latency_seconds",
+            "Time from submitting relayJob to CommonChain until it confirms",
+        ))
+        .unwrap();
+        let reassign_confirmation_latency = Histogram::with_opts(HistogramOpts::new(
+            "gateway_client_reassign_confirmation_latency_seconds",
+            "Time from submitting reassignGatewayRelay to CommonChain until it confirms",
+        ))
+        .unwrap();
+        let slash_timer_firings = IntCounterVec::new(
+            Opts::new(
+                "gateway_client_slash_timer_firings_total",
+                "Number of eventuality deadlines that fired without confirming, by kind",
+            ),
+            &["kind"],
+        )
+        .unwrap();
+        let job_retry_number = IntCounterVec::new(
+            Opts::new(
+                "gateway_client_job_retry_number_total",
+                "Distribution of job.retry_number across relayed jobs",
+            ),
+            &["retry_number"],
+        )
+        .unwrap();
+        let gateway_epoch_state_wait_latency = Histogram::with_opts(HistogramOpts::new(
+            "gateway_client_gateway_epoch_state_wait_latency_seconds",
+            "Time select_gateway_for_job_id spends waiting for its epoch cycle to be populated",
+        ))
+        .unwrap();
+        let relay_finality_rebroadcasts = IntCounter::new(
+            "gateway_client_relay_finality_rebroadcasts_total",
+            "Number of relayJob transactions re-broadcast after being reorged out or timing out before finality",
+        )
+        .unwrap();
+        let req_chain_active_ws_endpoint = IntGaugeVec::new(
+            Opts::new(
+                "gateway_client_req_chain_active_ws_endpoint",
+                "Index into ws_rpc_urls of the endpoint currently serving a request chain's subscriptions",
+            ),
+            &["req_chain_id"],
+        )
+        .unwrap();
+        let req_chain_ws_endpoint_consecutive_failures = IntGaugeVec::new(
+            Opts::new(
+                "gateway_client_req_chain_ws_endpoint_consecutive_failures",
+                "Consecutive failures recorded against a request chain's active WS endpoint",
+            ),
+            &["req_chain_id"],
+        )
+        .unwrap();
+
+        registry
+            .register(Box::new(jobs_received.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(gateway_selection_latency.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(relay_confirmation_latency.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(reassign_confirmation_latency.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(slash_timer_firings.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(job_retry_number.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(gateway_epoch_state_wait_latency.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(relay_finality_rebroadcasts.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(req_chain_active_ws_endpoint.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(req_chain_ws_endpoint_consecutive_failures.clone()))
+            .unwrap();
+
+        GatewayClientMetrics {
+            registry,
+            jobs_received,
+            gateway_selection_latency,
+            relay_confirmation_latency,
+            reassign_confirmation_latency,
+            slash_timer_firings,
+            job_retry_number,
+            gateway_epoch_state_wait_latency,
+            relay_finality_rebroadcasts,
+            req_chain_active_ws_endpoint,
+            req_chain_ws_endpoint_consecutive_failures,
+        }
+    }
+}
+
+async fn serve_client_req(
+    req: Request<Body>,
+    metrics: GatewayClientMetrics,
+) -> Result<Response<Body>, Infallible> {
+    if req.uri().path() != "/metrics" {
+        return Ok(Response::builder()
+            .status(404)
+            .body(Body::from("not found"))
+            .unwrap());
+    }
+
+    let metric_families = metrics.registry.gather();
+    let mut buffer = vec![];
+    TextEncoder::new()
+        .encode(&metric_families, &mut buffer)
+        .unwrap();
+
+    Ok(Response::new(Body::from(buffer)))
+}
+
+/// Spawn the `/metrics` HTTP endpoint on `addr`, serving `GatewayClientMetrics`
+/// in Prometheus text format.
+pub async fn serve_client_metrics(addr: SocketAddr, metrics: GatewayClientMetrics) {
+    let make_svc = make_service_fn(move |_conn| {
+        let metrics = metrics.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req| serve_client_req(req, metrics.clone())))
+        }
+    });
+
+    info!("Serving gateway client metrics on {}", addr);
+    if let Err(err) = Server::bind(&addr).serve(make_svc).await {
+        error!("Metrics server error: {:?}", err);
+    }
+}
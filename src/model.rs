@@ -9,10 +9,21 @@ use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::RwLock;
+use tokio::sync::watch;
 
+use crate::confirmation_buffer::ConfirmationBuffer;
 use crate::contract_abi::{
     CommonChainGatewayContract, CommonChainJobsContract, RequestChainContract,
 };
+use crate::dead_letter::DeadLetterStore;
+use crate::eventuality::EventualityTracker;
+use crate::gas_wallet::GasWallet;
+use crate::gateway_selector::GatewaySelector;
+use crate::job_store::JobStore;
+use crate::metrics::GatewayClientMetrics;
+use crate::provider_stack::ProviderStack;
+use crate::retry_policy::RetryPolicyTable;
+use crate::rpc_failover::FailoverEndpoints;
 use crate::HttpProvider;
 
 #[derive(Debug)]
@@ -24,6 +35,15 @@ pub struct AppState {
     pub common_chain_ws_url: String,
     pub gateway_contract_addr: Address,
     pub job_contract_addr: Address,
+    /// Factor applied to the Common Chain's sampled EIP-1559 fees, mirrored
+    /// from [`Config`].
+    pub gas_multiplier: f64,
+    /// Minimum `max_priority_fee_per_gas` offered on the Common Chain,
+    /// mirrored from [`Config`].
+    pub priority_fee_floor: U256,
+    /// Absolute max-fee ceiling on the Common Chain, mirrored from
+    /// [`Config`].
+    pub max_fee_cap: U256,
     pub chain_list: Mutex<Vec<RequestChainData>>,
     pub registered: Mutex<bool>,
     pub enclave_pub_key: Bytes,
@@ -64,6 +84,20 @@ pub struct Config {
     pub enclave_public_key: String,
     pub epoch: u64,
     pub time_interval: u64,
+    /// Per-chain ordered RPC endpoint lists, keyed by `chain_id`. Nothing
+    /// reads these two maps: `handle_all_req_chain_events` takes each
+    /// chain's endpoints from its `RequestChainData`.
+    pub req_chain_http_rpc_urls: HashMap<u64, Vec<String>>,
+    pub req_chain_ws_rpc_urls: HashMap<u64, Vec<String>>,
+    /// Factor applied to the Common Chain's sampled EIP-1559 fees before
+    /// they're submitted with a `JobRelay`/`SlashGatewayJob` transaction.
+    pub gas_multiplier: f64,
+    /// Minimum `max_priority_fee_per_gas` offered on the Common Chain
+    /// regardless of what was sampled.
+    pub priority_fee_floor: U256,
+    /// Absolute ceiling neither fee may exceed on the Common Chain
+    /// regardless of congestion.
+    pub max_fee_cap: U256,
 }
 
 #[derive(Debug, Clone)]
@@ -75,38 +109,151 @@ pub struct GatewayData {
     pub req_chain_ids: BTreeSet<u64>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct CommonChainClient {
-    pub signer: LocalWallet,
     pub enclave_signer_key: SigningKey,
+    /// This gateway's registered identity, derived from the enclave public
+    /// key at construction and never rotated: on-chain `gateway_address`
+    /// fields (job assignment, gateway reassignment) are compared against
+    /// this, not against whichever key currently pays gas, since rotating
+    /// the gas wallet must not make the gateway stop recognizing jobs
+    /// assigned to its registered address.
     pub address: Address,
     pub chain_ws_client: Provider<Ws>,
     pub gateway_contract_addr: H160,
     pub jobs_contract_addr: H160,
     pub gateway_contract: CommonChainGatewayContract<HttpProvider>,
     pub com_chain_jobs_contract: CommonChainJobsContract<HttpProvider>,
+    /// The gas-paying signer, its on-chain address, and the
+    /// nonce-manager/gas-oracle preparation built on top of it, bundled so
+    /// `rotate_gas_wallet` can swap all three atomically instead of leaving
+    /// one lagging behind when the operator's paying key changes.
+    pub gas_wallet: Arc<tokio::sync::RwLock<GasWallet>>,
+    /// Held as a read lock around every Common Chain relay and reassign
+    /// submission (request-chain `job_response` sends do not take it) and as a
+    /// write lock by `rotate_gas_wallet`, so a rotation in progress blocks
+    /// new submissions from starting against a half-swapped wallet without
+    /// interrupting ones already under way.
+    pub gas_wallet_rotation_barrier: Arc<tokio::sync::RwLock<()>>,
     pub req_chain_clients: HashMap<u64, Arc<RequestChainClient>>,
     pub gateway_epoch_state: Arc<RwLock<BTreeMap<u64, BTreeMap<Address, GatewayData>>>>,
+    /// Highest epoch cycle number passed to `notify_cycle_ready` so far.
+    /// `select_gateway_for_job_id` awaits a change on this, re-checking
+    /// `gateway_epoch_state` at least every
+    /// `GATEWAY_EPOCH_STATE_NOTIFY_TIMEOUT`. The gateway-epoch-state service
+    /// does not call `notify_cycle_ready`, so without another caller this
+    /// stays 0 and the timeout sets the re-check interval.
+    pub gateway_epoch_state_ready: watch::Sender<u64>,
     pub request_chain_list: Vec<u64>,
-    pub active_jobs: Arc<RwLock<HashMap<U256, Job>>>,
+    /// Identifier for this process incarnation, generated once at
+    /// construction, so a response lease left behind by a crashed prior
+    /// incarnation is distinguishable from the current one after a restart.
+    pub runner_id: String,
     pub epoch: u64,
     pub time_interval: u64,
     pub gateway_epoch_state_waitlist: Arc<RwLock<HashMap<u64, Vec<Job>>>>,
+    /// Durable backing store for every in-flight job and in-flight response,
+    /// replacing an in-memory `active_jobs` map so a restart can replay
+    /// `list_active` and resume relay/slash tracking instead of orphaning
+    /// everything a crash interrupted.
+    pub job_store: Arc<dyn JobStore>,
+    /// Events that failed to decode or referenced a job this gateway has no
+    /// record of, recorded here instead of unwrapping so a malformed or
+    /// out-of-order log can't take down the event task; kept for later
+    /// inspection/replay.
+    pub dead_letter_store: Arc<dyn DeadLetterStore>,
+    /// Max attempts, backoff curve and jitter the response slash timer
+    /// consults to compute a job response's next retry deadline and decide
+    /// when to give up, replacing the constants it used to bake in directly
+    /// so operators can tune timing per `ReqChainJobType` without
+    /// recompiling.
+    pub retry_policy: Arc<RetryPolicyTable>,
+    /// Assignment policy used to pick which gateway relays a job (or a
+    /// retry of one), chosen at construction so operators can tune it
+    /// without rewriting the relay core.
+    pub gateway_selector: Arc<dyn GatewaySelector>,
+    /// Shared poller for every outstanding relay-slash and response-slash
+    /// deadline, replacing a `tokio::time::sleep` task per job.
+    pub eventuality_tracker: Arc<EventualityTracker>,
+    /// Holds `JobResponded`/`JobResourceUnavailable` logs from the Common
+    /// Chain back until they're buried under `COMMON_CHAIN_CONFIRMATION_DEPTH`
+    /// confirmations, so a short reorg can't cause a job response to be
+    /// acted on and then orphaned.
+    pub com_chain_confirmation_buffer: Arc<RwLock<ConfirmationBuffer>>,
+    /// Prometheus counters/histograms for job intake, gateway-selection and
+    /// relay/reassign confirmation latency, slash-timer firings and retry
+    /// distribution, exposed over `/metrics`.
+    pub metrics: Arc<GatewayClientMetrics>,
+}
+
+impl std::fmt::Debug for CommonChainClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommonChainClient")
+            .field("address", &self.address)
+            .field("gateway_contract_addr", &self.gateway_contract_addr)
+            .field("jobs_contract_addr", &self.jobs_contract_addr)
+            .field("request_chain_list", &self.request_chain_list)
+            .field("epoch", &self.epoch)
+            .field("time_interval", &self.time_interval)
+            .finish()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct RequestChainData {
     pub chain_id: u64,
     pub contract_address: Address,
-    pub http_rpc_url: String,
+    /// HTTP RPC endpoints for this chain. `handle_all_req_chain_events` uses
+    /// only the first entry; the others are not tried, and there is no
+    /// failover or health tracking for the HTTP side.
+    pub http_rpc_urls: Vec<String>,
+    /// Ordered WebSocket RPC endpoints backing this chain's log/block
+    /// subscriptions. Wrapped in a `FailoverEndpoints`, which rotates to the
+    /// next entry after `MAX_CONSECUTIVE_FAILURES` consecutive failures.
+    pub ws_rpc_urls: Vec<String>,
+    /// Confirmations a `JobRelayed`/`JobCancelled`/`GatewayReassigned` log on
+    /// this chain must accumulate before it's dispatched to a handler.
+    /// Chains differ in finality time, so this is per-`RequestChainData`
+    /// rather than a single crate-wide constant.
+    pub confirmation_depth: u64,
+    /// Factor applied to this chain's sampled EIP-1559 fees before a
+    /// `job_response` transaction is submitted.
+    pub gas_multiplier: f64,
+    /// Minimum `max_priority_fee_per_gas` offered on this chain regardless
+    /// of what was sampled.
+    pub priority_fee_floor: U256,
+    /// Absolute ceiling neither fee may exceed on this chain regardless of
+    /// congestion.
+    pub max_fee_cap: U256,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct RequestChainClient {
     pub chain_id: u64,
     pub contract_address: Address,
-    pub ws_rpc_url: String,
+    /// Failover-aware WebSocket endpoint list backing this chain's log and
+    /// block subscriptions. Held onto (rather than just the URL used at
+    /// connection time) so the subscription tasks in
+    /// `handle_all_req_chain_events` can call `record_failure`/
+    /// `record_success` and reconnect against `current_url()` on transport
+    /// error or a stale-block timeout, and so operators can read
+    /// `health_snapshot` to see which endpoint is active.
+    pub ws_rpc_failover: Arc<FailoverEndpoints>,
     pub contract: RequestChainContract<HttpProvider>,
+    /// Shared nonce-manager and gas-oracle preparation for every
+    /// `job_response` transaction on this chain, independent of the Common
+    /// Chain's and every other request chain's since nonces and fee
+    /// conventions don't carry across chains.
+    pub provider_stack: Arc<ProviderStack>,
+}
+
+impl std::fmt::Debug for RequestChainClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestChainClient")
+            .field("chain_id", &self.chain_id)
+            .field("contract_address", &self.contract_address)
+            .finish()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -118,21 +265,23 @@ pub enum ComChainJobType {
 #[derive(Debug, Clone, PartialEq)]
 pub enum ReqChainJobType {
     JobResponded,
-    // SlashGatewayResponse,
+    SlashGatewayResponse,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Job {
     pub job_id: U256,
     pub req_chain_id: u64,
-    pub job_key: U256,
     pub tx_hash: FixedBytes,
     pub code_input: Bytes,
-    pub user_timeout: U256,
+    pub user_timout: U256,
     pub starttime: U256,
+    pub max_gas_price: U256,
+    pub deposit: Address,
+    pub callback_deposit: U256,
     pub job_owner: Address,
     pub job_type: ComChainJobType,
-    pub sequence_number: u8,
+    pub retry_number: u8,
     pub gateway_address: Option<Address>,
 }
 
@@ -140,12 +289,17 @@ pub struct Job {
 pub struct JobResponse {
     pub job_id: U256,
     pub req_chain_id: u64,
-    pub job_key: U256,
     pub output: Bytes,
     pub total_time: U256,
     pub error_code: u8,
     pub output_count: u8,
     pub job_type: ReqChainJobType,
     pub gateway_address: Option<Address>,
-    pub sequence_number: u8,
+    pub retry_number: u8,
+    /// Unix timestamp the `RetryPolicy` computed for this attempt, persisted
+    /// alongside the response lease. `finish_job_response_assignment`
+    /// recomputes it from the current time on every assignment, including
+    /// one made for a lease reclaimed after a restart, so the stored value
+    /// is not reused as the deadline.
+    pub next_attempt_at: u64,
 }
@@ -0,0 +1,83 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::{Context, Result};
+use ethers::providers::Middleware;
+use ethers::types::{Address, BlockNumber, U256};
+
+/// Hands out monotonically increasing nonces for one signer address, so
+/// concurrent transaction submissions never race each other's
+/// `eth_getTransactionCount("pending")` call and collide on the same nonce.
+/// One instance is shared across every outbound transaction for that address
+/// on a given chain — `CommonChainClient` holds one for its own address,
+/// and each `RequestChainClient` holds one for its chain — mirroring ethers'
+/// own nonce-manager middleware but explicit, so it can be shared across
+/// call sites (`gateway_contract`, `com_chain_jobs_contract`) that don't
+/// share a single provider stack.
+pub struct NonceManager {
+    address: Address,
+    next: AtomicU64,
+}
+
+impl NonceManager {
+    /// Seed the counter from `provider`'s pending nonce for `address`.
+    pub async fn new<M: Middleware>(provider: &M, address: Address) -> Result<Self> {
+        let nonce = provider
+            .get_transaction_count(address, Some(BlockNumber::Pending.into()))
+            .await
+            .context("failed to fetch initial nonce")?;
+        Ok(NonceManager {
+            address,
+            next: AtomicU64::new(nonce.as_u64()),
+        })
+    }
+
+    /// Hand out the next nonce, to be filled into an outbound transaction
+    /// before it's signed and sent.
+    pub fn next_nonce(&self) -> U256 {
+        U256::from(self.next.fetch_add(1, Ordering::SeqCst))
+    }
+
+    /// Resync the counter from `provider`, e.g. after a "nonce too low"/
+    /// "replacement underpriced" rejection, instead of continuing to hand
+    /// out nonces the chain has already rejected.
+    pub async fn resync<M: Middleware>(&self, provider: &M) -> Result<()> {
+        let nonce = provider
+            .get_transaction_count(self.address, Some(BlockNumber::Pending.into()))
+            .await
+            .context("failed to resync nonce")?;
+        self.next.store(nonce.as_u64(), Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+/// Whether an RPC rejection looks like a stale-nonce error `NonceManager`
+/// should recover from by resyncing, rather than a fee or connectivity
+/// problem the caller already handles separately.
+pub fn is_nonce_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("nonce too low") || message.contains("replacement transaction underpriced")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_nonce_increments_monotonically() {
+        let manager = NonceManager {
+            address: Address::zero(),
+            next: AtomicU64::new(5),
+        };
+        assert_eq!(manager.next_nonce(), U256::from(5u64));
+        assert_eq!(manager.next_nonce(), U256::from(6u64));
+        assert_eq!(manager.next_nonce(), U256::from(7u64));
+    }
+
+    #[test]
+    fn is_nonce_error_matches_known_rejections() {
+        assert!(is_nonce_error("Nonce too low"));
+        assert!(is_nonce_error("REPLACEMENT TRANSACTION UNDERPRICED"));
+        assert!(!is_nonce_error("insufficient funds for gas * price + value"));
+        assert!(!is_nonce_error("execution reverted"));
+    }
+}
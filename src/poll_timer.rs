@@ -0,0 +1,102 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// How long a `with_poll_timer`-wrapped future may stay `Pending` before its
+/// first "still waiting" warning, and the interval between repeats
+/// thereafter, so a stuck or throttled RPC endpoint produces a signal
+/// instead of hanging silently.
+pub const POLL_WARN_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Upper bound `with_timeout` enforces on the RPC/transaction awaits it
+/// wraps in this chunk, past which the await is aborted and surfaced as
+/// `RpcTimeout` instead of stalling the job task indefinitely.
+pub const RPC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Wraps a future so a poll that returns `Pending` for longer than
+/// `POLL_WARN_INTERVAL` (and every `POLL_WARN_INTERVAL` after that) logs a
+/// warning carrying the surrounding tracing span's fields. Modeled on
+/// pict-rs's `WithPollTimer`.
+pub struct PollTimer<F> {
+    future: F,
+    name: &'static str,
+    started: Option<Instant>,
+    last_warned: Option<Instant>,
+}
+
+impl<F: Future> Future for PollTimer<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: `future` is never moved out of `self` while pinned; only
+        // `Pin::new_unchecked` is used to re-pin it for the inner poll.
+        let this = unsafe { self.get_unchecked_mut() };
+        let started = *this.started.get_or_insert_with(Instant::now);
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+
+        match future.poll(cx) {
+            Poll::Ready(output) => Poll::Ready(output),
+            Poll::Pending => {
+                let now = Instant::now();
+                let due = this.last_warned.unwrap_or(started) + POLL_WARN_INTERVAL;
+                if now >= due {
+                    tracing::warn!(
+                        await_name = this.name,
+                        pending_secs = now.duration_since(started).as_secs(),
+                        "await has been pending longer than expected",
+                    );
+                    this.last_warned = Some(now);
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Extension trait bringing `with_poll_timer` to every future, so call
+/// sites can opt in with a single combinator instead of hand-rolling the
+/// pin-projection.
+pub trait WithPollTimer: Future + Sized {
+    fn with_poll_timer(self, name: &'static str) -> PollTimer<Self> {
+        PollTimer {
+            future: self,
+            name,
+            started: None,
+            last_warned: None,
+        }
+    }
+}
+
+impl<F: Future> WithPollTimer for F {}
+
+/// Typed error `with_timeout` surfaces, distinguishable from the generic
+/// `anyhow::Error` used elsewhere so the retry path can match on it instead
+/// of a stringly-typed message.
+#[derive(Debug)]
+pub struct RpcTimeout {
+    pub name: &'static str,
+    pub after: Duration,
+}
+
+impl fmt::Display for RpcTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} timed out after {:?}", self.name, self.after)
+    }
+}
+
+impl std::error::Error for RpcTimeout {}
+
+/// Bound `future` to `timeout`, aborting and surfacing `RpcTimeout` instead
+/// of letting a stuck or throttled RPC endpoint hang the caller
+/// indefinitely.
+pub async fn with_timeout<F: Future>(
+    name: &'static str,
+    timeout: Duration,
+    future: F,
+) -> Result<F::Output, RpcTimeout> {
+    tokio::time::timeout(timeout, future)
+        .await
+        .map_err(|_| RpcTimeout { name, after: timeout })
+}
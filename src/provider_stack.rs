@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use ethers::providers::Middleware;
+use ethers::types::transaction::eip1559::Eip1559TransactionRequest;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use log::debug;
+
+use crate::gas_oracle::GasOracle;
+use crate::nonce_manager::NonceManager;
+
+/// The gas-oracle and nonce-manager legs of every outbound transaction's
+/// preparation, collapsed into one shared entrypoint instead of each
+/// contract-call site populating a nonce and fees by hand. A signer and the
+/// base HTTP transport still wrap the underlying provider itself (via
+/// ethers' own `.with_signer()`, applied once when the provider is built),
+/// and a resubmit/retry layer still wraps this at the call site
+/// (`TxnManager`'s fee-bump loop, and `job_response_txn`'s nonce-retry
+/// loop) since retrying a confirmation timeout needs to keep a bumped fee
+/// rather than re-sample the oracle on every attempt. The full chain is
+/// therefore, outside in: retry → gas-oracle → nonce-manager → signer →
+/// base transport — mirroring ethers' middleware layering without
+/// reaching for its `Middleware` associated-type machinery, which nothing
+/// else in this crate implements.
+#[derive(Debug, Clone)]
+pub struct ProviderStack {
+    pub gas_oracle: Arc<GasOracle>,
+    pub nonce_manager: Arc<NonceManager>,
+}
+
+impl ProviderStack {
+    pub fn new(gas_oracle: Arc<GasOracle>, nonce_manager: Arc<NonceManager>) -> Self {
+        ProviderStack {
+            gas_oracle,
+            nonce_manager,
+        }
+    }
+
+    /// Fill `tx`'s nonce and EIP-1559 fees in one call, the shared
+    /// preparation every `CommonChainGatewayContract`/`CommonChainJobsContract`/
+    /// `RequestChainContract` submission now goes through instead of
+    /// duplicating the nonce-manager and gas-oracle calls per call site.
+    pub async fn prepare<M: Middleware>(&self, provider: &M, tx: &mut TypedTransaction) -> Result<()> {
+        ensure_eip1559(tx);
+
+        tx.set_nonce(self.nonce_manager.next_nonce());
+
+        let (max_fee_per_gas, max_priority_fee_per_gas) = self.gas_oracle.estimate_fees(provider).await?;
+        if let Some(eip1559) = tx.as_eip1559_mut() {
+            eip1559.max_fee_per_gas = Some(max_fee_per_gas);
+            eip1559.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+        } else {
+            debug!("TypedTransaction did not convert to the Eip1559 variant; falling back to legacy gas_price");
+            tx.set_gas_price(max_fee_per_gas);
+        }
+
+        Ok(())
+    }
+}
+
+/// Every submission in this crate is meant to go out as a type-2 (EIP-1559)
+/// transaction, but ethers' abigen-generated `ContractCall`s build their
+/// `TypedTransaction` as the `Legacy` variant by default, so `as_eip1559_mut()`
+/// would otherwise always return `None` here and fees would silently fall
+/// back to a legacy gas price. Rebuild `tx` as an `Eip1559TransactionRequest`
+/// up front, carrying over the fields common to every variant, so the fee
+/// fields set above actually land on the transaction that gets signed.
+fn ensure_eip1559(tx: &mut TypedTransaction) {
+    if matches!(tx, TypedTransaction::Eip1559(_)) {
+        return;
+    }
+
+    let mut eip1559 = Eip1559TransactionRequest::new();
+    if let Some(from) = tx.from() {
+        eip1559 = eip1559.from(*from);
+    }
+    if let Some(to) = tx.to() {
+        eip1559 = eip1559.to(to.clone());
+    }
+    if let Some(gas) = tx.gas() {
+        eip1559 = eip1559.gas(*gas);
+    }
+    if let Some(value) = tx.value() {
+        eip1559 = eip1559.value(*value);
+    }
+    if let Some(data) = tx.data() {
+        eip1559 = eip1559.data(data.clone());
+    }
+    if let Some(nonce) = tx.nonce() {
+        eip1559 = eip1559.nonce(*nonce);
+    }
+    if let Some(chain_id) = tx.chain_id() {
+        eip1559 = eip1559.chain_id(chain_id.as_u64());
+    }
+
+    *tx = TypedTransaction::Eip1559(eip1559);
+}
@@ -0,0 +1,103 @@
+use anyhow::Result;
+use ethers::prelude::*;
+use ethers::types::Bytes;
+use log::warn;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::common_chain_gateway_state_service::GatewayData;
+use crate::state_store::{CycleBlockHash, StateStore};
+
+/// Cycles whose `to_block_number` is within `reorg_depth` confirmations of
+/// the chain head are treated as mutable: before extending from them, their
+/// recorded block hash is re-checked against the canonical chain. Cycles
+/// older than that are assumed final and are never re-checked.
+pub async fn repair_reorg(
+    provider: &Provider<Http>,
+    gateway_epoch_state: &Arc<RwLock<BTreeMap<u64, BTreeMap<Bytes, GatewayData>>>>,
+    cycle_block_hashes: &Arc<RwLock<BTreeMap<u64, CycleBlockHash>>>,
+    state_store: &Arc<dyn StateStore>,
+    reorg_depth: u64,
+) -> Result<()> {
+    let head_block_number = provider.get_block_number().await?.as_u64();
+
+    let known_cycles: Vec<u64> = cycle_block_hashes.read().await.keys().cloned().collect();
+
+    // walk from the most recent cycle backward, stopping at the first cycle
+    // whose recorded hash still matches canonical (or that is beyond the
+    // configurable reorg depth, and therefore assumed final)
+    for cycle in known_cycles.into_iter().rev() {
+        // `to_block_number` is tracked directly on the record rather than
+        // derived from `gateway_epoch_state`'s `GatewayData`, so the check
+        // still runs for cycles that ended up with zero registered
+        // gateways instead of silently skipping them.
+        let (to_block_number, recorded_hash) = {
+            let cycle_block_hashes_guard = cycle_block_hashes.read().await;
+            match cycle_block_hashes_guard.get(&cycle) {
+                Some(record) => (record.to_block_number, record.block_hash),
+                None => continue,
+            }
+        };
+
+        if head_block_number.saturating_sub(to_block_number) > reorg_depth {
+            // beyond the mutable window: assumed final, stop walking further back
+            break;
+        }
+
+        let canonical_hash = provider
+            .get_block(to_block_number)
+            .await?
+            .map(|block| block.hash.unwrap_or_default())
+            .unwrap_or_default();
+
+        if canonical_hash == recorded_hash {
+            // first still-canonical cycle found; everything before it is fine
+            break;
+        }
+
+        warn!(
+            "Detected reorg at block {} (cycle {}): recorded hash {:?}, canonical hash {:?}. Discarding cycles from {} onward.",
+            to_block_number, cycle, recorded_hash, canonical_hash, cycle
+        );
+
+        // discard this cycle and everything after it, in memory and in the store
+        {
+            let mut gateway_epoch_state_guard = gateway_epoch_state.write().await;
+            gateway_epoch_state_guard.retain(|c, _| *c < cycle);
+        }
+        {
+            let mut cycle_block_hashes_guard = cycle_block_hashes.write().await;
+            cycle_block_hashes_guard.retain(|c, _| *c < cycle);
+        }
+        state_store.prune_from(cycle).await?;
+    }
+
+    Ok(())
+}
+
+/// Record the canonical block hash observed at `to_block_number` for `cycle`,
+/// so a later reorg check has something to compare against. Persisted to
+/// `state_store` alongside the in-memory copy so a restart resumes with the
+/// hash still available instead of reorg-checking resumed cycles against
+/// nothing until fresh cycles accumulate.
+pub async fn record_cycle_block_hash(
+    provider: &Provider<Http>,
+    cycle_block_hashes: &Arc<RwLock<BTreeMap<u64, CycleBlockHash>>>,
+    state_store: &Arc<dyn StateStore>,
+    cycle: u64,
+    to_block_number: u64,
+) -> Result<()> {
+    let block_hash = provider
+        .get_block(to_block_number)
+        .await?
+        .map(|block| block.hash.unwrap_or_default())
+        .unwrap_or_default();
+    let record = CycleBlockHash {
+        to_block_number,
+        block_hash,
+    };
+    cycle_block_hashes.write().await.insert(cycle, record);
+    state_store.put_cycle_block_hash(cycle, record).await?;
+    Ok(())
+}
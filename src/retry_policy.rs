@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::constant::MAX_GATEWAY_RETRIES;
+use crate::model::ReqChainJobType;
+
+/// Max attempts, delay curve and jitter governing one slash-timer's retry
+/// behavior. Replaces the `RESPONSE_RELAY_TIMEOUT`/`MAX_GATEWAY_RETRIES`
+/// constants `JobResponseEventuality` used to consult directly, so operators
+/// can tune timing per job type without recompiling.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u8,
+    pub base_delay: Duration,
+    /// Factor the delay grows by per retry; `1.0` reproduces the old flat
+    /// `RESPONSE_RELAY_TIMEOUT` wait, values above `1.0` back off
+    /// exponentially.
+    pub multiplier: f64,
+    /// Maximum random perturbation applied in either direction, so retries
+    /// across many jobs don't all land on the same instant. `None` disables
+    /// jitter entirely.
+    pub jitter: Option<Duration>,
+}
+
+impl RetryPolicy {
+    /// Whether a job already on its `retry_number`-th attempt should stop
+    /// retrying instead of being reassigned again.
+    pub fn exhausted(&self, retry_number: u8) -> bool {
+        retry_number >= self.max_attempts
+    }
+
+    /// Delay before the attempt numbered `retry_number` (0-indexed), growing
+    /// by `multiplier` each retry and perturbed by `jitter` if set.
+    pub fn delay_for(&self, retry_number: u8) -> Duration {
+        let scaled = self.base_delay.as_secs_f64() * self.multiplier.powi(retry_number as i32);
+        let delay = scaled.max(0.0);
+        let jittered = match self.jitter {
+            Some(jitter) => {
+                let jitter_secs = jitter.as_secs_f64();
+                let offset = rand::thread_rng().gen_range(-jitter_secs..=jitter_secs);
+                (delay + offset).max(0.0)
+            }
+            None => delay,
+        };
+        Duration::from_secs_f64(jittered)
+    }
+
+    /// Absolute unix timestamp `delay_for(retry_number)` past `now`, stored
+    /// as a job response's `next_attempt_at`.
+    pub fn next_attempt_at(&self, now: u64, retry_number: u8) -> u64 {
+        now + self.delay_for(retry_number).as_secs()
+    }
+}
+
+/// Per-`ReqChainJobType` retry policies consulted by the response slash
+/// timer, so a `JobResponded` retry and a `SlashGatewayResponse` retry can be
+/// tuned independently instead of sharing one pair of constants.
+#[derive(Debug, Clone)]
+pub struct RetryPolicyTable {
+    pub job_responded: RetryPolicy,
+    pub slash_gateway_response: RetryPolicy,
+}
+
+impl RetryPolicyTable {
+    pub fn policy_for(&self, job_type: &ReqChainJobType) -> &RetryPolicy {
+        match job_type {
+            ReqChainJobType::JobResponded => &self.job_responded,
+            ReqChainJobType::SlashGatewayResponse => &self.slash_gateway_response,
+        }
+    }
+}
+
+impl Default for RetryPolicyTable {
+    fn default() -> Self {
+        // Mirrors the constants this replaces: a flat 40s delay and two max
+        // attempts, so existing deployments see unchanged behavior until an
+        // operator opts into real backoff.
+        let default_policy = RetryPolicy {
+            max_attempts: MAX_GATEWAY_RETRIES,
+            base_delay: Duration::from_secs(40),
+            multiplier: 1.0,
+            jitter: None,
+        };
+        RetryPolicyTable {
+            job_responded: default_policy.clone(),
+            slash_gateway_response: default_policy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exhausted_stops_at_max_attempts() {
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_secs(1),
+            multiplier: 1.0,
+            jitter: None,
+        };
+        assert!(!policy.exhausted(0));
+        assert!(!policy.exhausted(2));
+        assert!(policy.exhausted(3));
+        assert!(policy.exhausted(4));
+    }
+
+    #[test]
+    fn delay_for_without_jitter_grows_by_multiplier() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: None,
+        };
+        assert_eq!(policy.delay_for(0), Duration::from_secs(10));
+        assert_eq!(policy.delay_for(1), Duration::from_secs(20));
+        assert_eq!(policy.delay_for(2), Duration::from_secs(40));
+    }
+
+    #[test]
+    fn delay_for_flat_multiplier_reproduces_base_delay() {
+        // multiplier 1.0 is what `RetryPolicyTable::default()` relies on to
+        // reproduce the old flat RESPONSE_RELAY_TIMEOUT wait.
+        let policy = RetryPolicy {
+            max_attempts: 2,
+            base_delay: Duration::from_secs(40),
+            multiplier: 1.0,
+            jitter: None,
+        };
+        assert_eq!(policy.delay_for(0), Duration::from_secs(40));
+        assert_eq!(policy.delay_for(3), Duration::from_secs(40));
+    }
+
+    #[test]
+    fn next_attempt_at_adds_delay_to_now() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: None,
+        };
+        assert_eq!(policy.next_attempt_at(1_000, 0), 1_010);
+        assert_eq!(policy.next_attempt_at(1_000, 1), 1_020);
+    }
+
+    #[test]
+    fn policy_for_selects_the_matching_job_type() {
+        let table = RetryPolicyTable {
+            job_responded: RetryPolicy {
+                max_attempts: 1,
+                base_delay: Duration::from_secs(1),
+                multiplier: 1.0,
+                jitter: None,
+            },
+            slash_gateway_response: RetryPolicy {
+                max_attempts: 9,
+                base_delay: Duration::from_secs(2),
+                multiplier: 1.0,
+                jitter: None,
+            },
+        };
+        assert_eq!(
+            table.policy_for(&ReqChainJobType::JobResponded).max_attempts,
+            1
+        );
+        assert_eq!(
+            table
+                .policy_for(&ReqChainJobType::SlashGatewayResponse)
+                .max_attempts,
+            9
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::{bail, Result};
+use tokio::sync::RwLock;
+
+/// Health observed for a single RPC endpoint: the last block height a call
+/// against it actually produced, and how many consecutive calls have failed
+/// since then. Reset to 0 on the next success.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EndpointHealth {
+    pub last_successful_block: Option<u64>,
+    pub consecutive_failures: u32,
+}
+
+/// Consecutive failures (transport errors, or a subscription gone stale
+/// from no new blocks) an endpoint may accrue before `record_failure`
+/// rotates to the next one in the list.
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+/// Ordered list of RPC endpoints for one request chain's HTTP or
+/// WebSocket transport, with per-endpoint health tracking and failover
+/// rotation. Replaces a single hardcoded endpoint URL: a flaky Infura or
+/// public endpoint no longer takes the chain offline, it just gets skipped
+/// in favour of the next one in the list, wrapping back to the first once
+/// every endpoint has been tried.
+pub struct FailoverEndpoints {
+    urls: Vec<String>,
+    active: AtomicUsize,
+    health: RwLock<Vec<EndpointHealth>>,
+}
+
+impl FailoverEndpoints {
+    pub fn new(urls: Vec<String>) -> Result<Self> {
+        if urls.is_empty() {
+            bail!("at least one RPC endpoint is required");
+        }
+        let health = vec![EndpointHealth::default(); urls.len()];
+        Ok(FailoverEndpoints {
+            urls,
+            active: AtomicUsize::new(0),
+            health: RwLock::new(health),
+        })
+    }
+
+    /// The endpoint currently in use.
+    pub fn current_url(&self) -> String {
+        self.urls[self.active.load(Ordering::SeqCst)].clone()
+    }
+
+    /// Index of the endpoint currently in use, for metrics.
+    pub fn active_index(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+
+    /// Record a successful call against the active endpoint at
+    /// `block_number`, clearing its failure count.
+    pub async fn record_success(&self, block_number: u64) {
+        let index = self.active.load(Ordering::SeqCst);
+        let mut health = self.health.write().await;
+        health[index].last_successful_block = Some(block_number);
+        health[index].consecutive_failures = 0;
+    }
+
+    /// Record a failed call (transport error, or a stale-block timeout)
+    /// against the active endpoint. Returns `true` if this failure pushed
+    /// it past [`MAX_CONSECUTIVE_FAILURES`] and rotated to the next one, so
+    /// the caller knows to reconnect/re-subscribe against `current_url()`.
+    pub async fn record_failure(&self) -> bool {
+        let index = self.active.load(Ordering::SeqCst);
+        let mut health = self.health.write().await;
+        health[index].consecutive_failures += 1;
+        if health[index].consecutive_failures < MAX_CONSECUTIVE_FAILURES {
+            return false;
+        }
+
+        let next = (index + 1) % self.urls.len();
+        self.active.store(next, Ordering::SeqCst);
+        true
+    }
+
+    /// Snapshot of every endpoint's URL and observed health, in list order,
+    /// so operators can see which one is active and why a failover
+    /// happened.
+    pub async fn health_snapshot(&self) -> Vec<(String, EndpointHealth)> {
+        let health = self.health.read().await;
+        self.urls
+            .iter()
+            .cloned()
+            .zip(health.iter().copied())
+            .collect()
+    }
+}
@@ -0,0 +1,129 @@
+use anyhow::{bail, Context, Result};
+use ethers::contract::ContractCall;
+use ethers::prelude::*;
+use ethers::types::{TransactionReceipt, U256};
+use log::{debug, info, warn};
+use std::time::Duration;
+use tokio::time;
+
+use crate::nonce_manager::is_nonce_error;
+use crate::provider_stack::ProviderStack;
+
+/// How long to wait for a submitted transaction to confirm before bumping
+/// fees and resubmitting on the same nonce.
+const RESUBMIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Numerator/denominator of the fee bump applied on every resubmission:
+/// 1125 / 1000 is a 12.5% bump, matching the minimum replace-by-fee increase
+/// most miners/relays require.
+const FEE_BUMP_NUMERATOR: u64 = 1125;
+const FEE_BUMP_DENOMINATOR: u64 = 1000;
+
+/// Sends a contract call with an EIP-1559 fee strategy instead of the bare
+/// `txn.send()` + `confirmations(1)` used previously, which ignored the
+/// job's `max_gas_price` and could stall forever behind a stuck nonce.
+pub struct TxnManager;
+
+impl TxnManager {
+    /// Populate `call` with a nonce and EIP-1559 fees from `provider_stack`
+    /// (capped at `max_fee_price`), submit it, and keep resubmitting the
+    /// same nonce with fees bumped ~12.5% every [`RESUBMIT_TIMEOUT`] until
+    /// it confirms or a bump would exceed `max_fee_price`, in which case
+    /// this returns an error instead of giving up silently. A "nonce too
+    /// low"/"replacement underpriced" rejection on submission resyncs
+    /// `provider_stack`'s nonce manager and retries with a fresh nonce
+    /// instead of bailing out.
+    pub async fn send<M, D>(
+        provider: &M,
+        provider_stack: &ProviderStack,
+        mut call: ContractCall<M, D>,
+        max_fee_price: U256,
+    ) -> Result<TransactionReceipt>
+    where
+        M: Middleware + 'static,
+        D: ethers::abi::Detokenize,
+    {
+        provider_stack
+            .prepare(provider, &mut call.tx)
+            .await
+            .context("failed to prepare transaction nonce/fees")?;
+        let mut nonce = call.tx.nonce().copied().unwrap_or_default();
+
+        let (mut max_fee_per_gas, mut max_priority_fee_per_gas) = match call.tx.as_eip1559() {
+            Some(eip1559) => (
+                eip1559.max_fee_per_gas.unwrap_or_default(),
+                eip1559.max_priority_fee_per_gas.unwrap_or_default(),
+            ),
+            None => {
+                // `ProviderStack::prepare` converts every TypedTransaction to
+                // the Eip1559 variant before returning, so this branch
+                // should be unreachable; log it in case that conversion
+                // ever regresses, since the fallback below silently submits
+                // at legacy gas pricing instead of the type-2 fees the job
+                // actually requested.
+                debug!("ContractCall's TypedTransaction was not Eip1559 after ProviderStack::prepare; submitting with legacy gas_price");
+                let gas_price = call.tx.gas_price().unwrap_or_default();
+                (gas_price, gas_price)
+            }
+        };
+        max_fee_per_gas = max_fee_per_gas.min(max_fee_price);
+        max_priority_fee_per_gas = max_priority_fee_per_gas.min(max_fee_price);
+
+        loop {
+            if let Some(eip1559) = call.tx.as_eip1559_mut() {
+                eip1559.max_fee_per_gas = Some(max_fee_per_gas);
+                eip1559.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+            } else {
+                call.tx.set_gas_price(max_fee_per_gas);
+            }
+
+            let pending_txn = match call.send().await {
+                Ok(pending_txn) => pending_txn,
+                Err(err) if is_nonce_error(&err.to_string()) => {
+                    warn!(
+                        "Transaction rejected at nonce {} ({:?}); resyncing nonce manager",
+                        nonce, err
+                    );
+                    provider_stack
+                        .nonce_manager
+                        .resync(provider)
+                        .await
+                        .context("failed to resync nonce manager")?;
+                    nonce = provider_stack.nonce_manager.next_nonce();
+                    call.tx.set_nonce(nonce);
+                    continue;
+                }
+                Err(err) => bail!("failed to submit transaction: {:?}", err),
+            };
+            let txn_hash = pending_txn.tx_hash();
+            info!(
+                "Submitted transaction {:?} at nonce {} with max_fee_per_gas {}",
+                txn_hash, nonce, max_fee_per_gas
+            );
+
+            match time::timeout(RESUBMIT_TIMEOUT, pending_txn).await {
+                Ok(Ok(Some(receipt))) => return Ok(receipt),
+                Ok(Ok(None)) => bail!("transaction {:?} was dropped from the mempool", txn_hash),
+                Ok(Err(err)) => bail!("transaction {:?} failed: {:?}", txn_hash, err),
+                Err(_) => {
+                    let bumped_max_fee =
+                        max_fee_per_gas * FEE_BUMP_NUMERATOR / FEE_BUMP_DENOMINATOR;
+                    if bumped_max_fee > max_fee_price {
+                        bail!(
+                            "transaction {:?} unconfirmed after {:?}; bumping max_fee_per_gas to {} would exceed the job's max_gas_price {}",
+                            txn_hash, RESUBMIT_TIMEOUT, bumped_max_fee, max_fee_price
+                        );
+                    }
+                    warn!(
+                        "Transaction {:?} unconfirmed after {:?}; resubmitting nonce {} at bumped max_fee_per_gas {}",
+                        txn_hash, RESUBMIT_TIMEOUT, nonce, bumped_max_fee
+                    );
+                    max_fee_per_gas = bumped_max_fee;
+                    max_priority_fee_per_gas =
+                        (max_priority_fee_per_gas * FEE_BUMP_NUMERATOR / FEE_BUMP_DENOMINATOR)
+                            .min(max_fee_price);
+                }
+            }
+        }
+    }
+}